@@ -0,0 +1,447 @@
+//! A bump-arena whose boxes are only a 32-bit offset plus a 'slimmer' metadata.
+//!
+//! See [`SlimmerArena`] and [`SlimmerArenaBox`] for more information.
+use alloc::vec::Vec;
+use core::{
+    alloc::Layout,
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    num::NonZeroU32,
+    ops::Index,
+    ptr::NonNull,
+};
+use ptr_meta::Pointee;
+
+use crate::{
+    CloneUnsized, DefaultSlimmerMetadata, PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox,
+    SlimmerPointee,
+};
+
+/// The capacity of the first chunk an arena allocates.
+/// Every next chunk is (at least) twice as large as the previous one.
+const FIRST_CHUNK_CAPACITY: usize = 4096;
+
+/// Invariant in 'arena, so arenas (and their boxes) from different scopes can never be mixed up.
+type Brand<'arena> = PhantomData<fn(&'arena ()) -> &'arena ()>;
+
+/// A bump-allocating arena handing out [`SlimmerArenaBox`]es.
+///
+/// Values are copied into the arena using [`SlimmerArena::alloc_slim`].
+/// All values are freed at once when the arena goes out of scope.
+/// (Just like with most other bump arenas, their destructors are **not** run.)
+///
+/// The arena's memory consists of a list of chunks which are never moved,
+/// which together span at most `u32::MAX` bytes of offsets.
+///
+/// An arena can only be created using [`SlimmerArena::scope`], which gives each arena a unique `'arena` lifetime.
+/// This ensures a SlimmerArenaBox can only ever be resolved using the arena it was allocated in.
+///
+/// ```rust
+/// use slimmer_box::{SlimmerArena, SlimmerArenaBox, SlimmerBox};
+///
+/// let promoted: SlimmerBox<str> = SlimmerArena::scope(|arena| {
+///     let hello: SlimmerArenaBox<str> = arena.alloc_slim("hello");
+///     let numbers: SlimmerArenaBox<[u64]> = arena.alloc_slim(&[1, 2, 3][..]);
+///     assert_eq!(core::mem::size_of_val(&hello), 8);
+///
+///     assert_eq!(&arena[hello], "hello");
+///     assert_eq!(numbers.get(arena), &[1, 2, 3]);
+///
+///     hello.to_slimmer_box(arena)
+/// });
+/// assert_eq!(&*promoted, "hello");
+/// ```
+///
+/// Boxes from one arena cannot be used with another:
+/// ```compile_fail
+/// use slimmer_box::{SlimmerArena, SlimmerArenaBox};
+///
+/// SlimmerArena::scope(|first| {
+///     SlimmerArena::scope(|second| {
+///         let hello: SlimmerArenaBox<str> = first.alloc_slim("hello");
+///         second[hello].len()
+///     })
+/// });
+/// ```
+pub struct SlimmerArena<'arena> {
+    chunks: RefCell<Vec<Chunk>>,
+    // Number of bytes in use in the last chunk
+    used: Cell<usize>,
+    brand: Brand<'arena>,
+}
+
+struct Chunk {
+    ptr: NonNull<u8>,
+    // The offset of the first byte of this chunk
+    start: u32,
+    capacity: usize,
+}
+
+impl Chunk {
+    fn layout(capacity: usize) -> Layout {
+        Layout::from_size_align(capacity, 1).expect("allocation size overflow")
+    }
+
+    /// Returns the (chunk-local) position at which a value with the given layout would fit,
+    /// if it fits.
+    fn fit(&self, used: usize, layout: Layout) -> Option<usize> {
+        let base = self.ptr.as_ptr() as usize;
+        let pos = base.checked_add(used)?;
+        let aligned = pos.checked_add(layout.align() - 1)? & !(layout.align() - 1);
+        let local = aligned - base;
+        if local.checked_add(layout.size())? <= self.capacity {
+            Some(local)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'arena> SlimmerArena<'arena> {
+    /// Creates a new, empty arena, and passes it to the given closure.
+    ///
+    /// The arena (and all its contents) is dropped when the closure returns.
+    /// Values that should outlive the arena can be promoted to a SlimmerBox using [`SlimmerArenaBox::to_slimmer_box`].
+    ///
+    /// No memory is allocated until the first value is allocated in the arena.
+    pub fn scope<R, F>(f: F) -> R
+    where
+        F: for<'a> FnOnce(&SlimmerArena<'a>) -> R,
+    {
+        let arena = SlimmerArena {
+            chunks: RefCell::new(Vec::new()),
+            used: Cell::new(0),
+            brand: PhantomData,
+        };
+        f(&arena)
+    }
+
+    /// Copies the given value (which may be a slice, string or other dynamically sized type) into the arena.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata,
+    /// or if the arena has run out of 32-bit offsets.
+    pub fn alloc_slim<T, SlimmerMetadata>(
+        &self,
+        value: &T,
+    ) -> SlimmerArenaBox<'arena, T, SlimmerMetadata>
+    where
+        T: ?Sized,
+        T: CloneUnsized,
+        T: SlimmerPointee<SlimmerMetadata>,
+        SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    {
+        self.try_alloc_slim(value).unwrap()
+    }
+
+    /// Variant of `alloc_slim` which will return an error if the value's metadata is too large instead of panicing.
+    ///
+    /// Still panics if the arena has run out of 32-bit offsets.
+    pub fn try_alloc_slim<T, SlimmerMetadata>(
+        &self,
+        value: &T,
+    ) -> Result<
+        SlimmerArenaBox<'arena, T, SlimmerMetadata>,
+        PointerMetadataDoesNotFitError<T, SlimmerMetadata>,
+    >
+    where
+        T: ?Sized,
+        T: CloneUnsized,
+        T: SlimmerPointee<SlimmerMetadata>,
+        SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    {
+        let meta = ptr_meta::metadata(value);
+        let slim_meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
+        let (offset, ptr) = self.reserve(Layout::for_value(value));
+        // SAFETY: Freshly reserved space, large enough and properly aligned.
+        // If cloning panics, the reserved space is simply never used.
+        unsafe { value.clone_to_uninit(ptr) };
+
+        Ok(SlimmerArenaBox {
+            offset,
+            meta: slim_meta,
+            brand: PhantomData,
+            marker: PhantomData,
+        })
+    }
+
+    /// The total number of bytes of memory the arena has allocated.
+    pub fn allocated_bytes(&self) -> usize {
+        self.chunks
+            .borrow()
+            .iter()
+            .map(|chunk| chunk.capacity)
+            .sum()
+    }
+
+    /// Reserves room for a value with the given layout.
+    ///
+    /// NOTE: The RefCell is no longer borrowed when this function returns,
+    /// so cloning into the reserved room may re-entrantly allocate in the same arena.
+    fn reserve(&self, layout: Layout) -> (NonZeroU32, *mut u8) {
+        let mut chunks = self.chunks.borrow_mut();
+        let fits = chunks
+            .last()
+            .and_then(|chunk| chunk.fit(self.used.get(), layout));
+        let local = match fits {
+            Some(local) => local,
+            None => {
+                let chunk = Self::new_chunk(chunks.last(), layout);
+                chunks.push(chunk);
+                self.used.set(0);
+                // SAFETY: A new chunk always has enough room for `layout`
+                unsafe {
+                    chunks
+                        .last()
+                        .unwrap_unchecked()
+                        .fit(0, layout)
+                        .unwrap_unchecked()
+                }
+            }
+        };
+        // SAFETY: Just pushed, or checked above
+        let chunk = unsafe { chunks.last().unwrap_unchecked() };
+        self.used.set(local + layout.size());
+        // NOTE: Cannot overflow or be zero, as `start + capacity` was checked when creating the chunk
+        // and `start` is always at least 1.
+        let offset = unsafe { NonZeroU32::new_unchecked(chunk.start + local as u32) };
+        // SAFETY: `local` is within bounds of the chunk
+        (offset, unsafe { chunk.ptr.as_ptr().add(local) })
+    }
+
+    fn new_chunk(prev: Option<&Chunk>, layout: Layout) -> Chunk {
+        const FULL: &str = "SlimmerArena is full: offsets no longer fit in a u32";
+        // NOTE: Offset 0 is never used (allowing the Option<SlimmerArenaBox> niche),
+        // and chunks are separated by one offset so a zero-sized value at the very end of a chunk
+        // can never be mistaken for one at the start of the next chunk.
+        let start = match prev {
+            None => 1,
+            Some(prev) => (prev.start as usize + prev.capacity)
+                .checked_add(1)
+                .expect(FULL),
+        };
+        let min_capacity = layout
+            .size()
+            .checked_add(layout.align() - 1)
+            .expect("allocation size overflow");
+        let max_capacity = (u32::MAX as usize).checked_sub(start).expect(FULL);
+        if min_capacity > max_capacity || max_capacity == 0 {
+            panic!("{}", FULL)
+        }
+        let wanted_capacity = prev.map_or(FIRST_CHUNK_CAPACITY, |prev| prev.capacity * 2);
+        let capacity = wanted_capacity.max(min_capacity).min(max_capacity);
+
+        let chunk_layout = Chunk::layout(capacity);
+        // SAFETY: capacity is never zero, as both `wanted_capacity` and `max_capacity` are non-zero
+        let ptr = unsafe { alloc::alloc::alloc(chunk_layout) };
+        let ptr = match NonNull::new(ptr) {
+            Some(ptr) => ptr,
+            None => alloc::alloc::handle_alloc_error(chunk_layout),
+        };
+        Chunk {
+            ptr,
+            start: start as u32,
+            capacity,
+        }
+    }
+
+    /// Returns a pointer to the memory at the given offset.
+    fn resolve(&self, offset: NonZeroU32) -> *mut u8 {
+        let chunks = self.chunks.borrow();
+        let index = chunks.partition_point(|chunk| chunk.start <= offset.get()) - 1;
+        let chunk = &chunks[index];
+        // SAFETY: The offset was handed out by this arena (guaranteed by the 'arena lifetime), so is within bounds
+        unsafe {
+            chunk
+                .ptr
+                .as_ptr()
+                .add((offset.get() - chunk.start) as usize)
+        }
+    }
+}
+
+impl<'arena> Drop for SlimmerArena<'arena> {
+    fn drop(&mut self) {
+        for chunk in self.chunks.get_mut().iter() {
+            // SAFETY: Allocated in `new_chunk` with the same layout
+            unsafe { alloc::alloc::dealloc(chunk.ptr.as_ptr(), Chunk::layout(chunk.capacity)) }
+        }
+    }
+}
+
+impl<'arena> core::fmt::Debug for SlimmerArena<'arena> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SlimmerArena")
+            .field("chunks", &self.chunks.borrow().len())
+            .field("allocated_bytes", &self.allocated_bytes())
+            .finish()
+    }
+}
+
+/// A value living in a [`SlimmerArena`], stored as a 32-bit offset into the arena plus a 'slimmer' metadata.
+///
+/// A `SlimmerArenaBox<'arena, [T]>` (or `str`) therefore only takes up 8 bytes on all platforms,
+/// and because offset zero is never used, `Option<SlimmerArenaBox<'arena, [T]>>` takes up 8 bytes as well.
+///
+/// As it does not contain a pointer, accessing the value requires the arena it was allocated in:
+/// Either using [`SlimmerArenaBox::get`] or by indexing the arena (`arena[arena_box]`).
+///
+/// A SlimmerArenaBox does not own its value (the arena does), and is therefore [`Copy`].
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerArenaBox<
+    'arena,
+    T,
+    SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata,
+> where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    offset: NonZeroU32,
+    meta: SlimmerMetadata,
+    brand: Brand<'arena>,
+    marker: PhantomData<&'arena T>,
+}
+
+impl<'arena, T, SlimmerMetadata> SlimmerArenaBox<'arena, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Resolves the value using the arena it was allocated in.
+    pub fn get<'a>(self, arena: &'a SlimmerArena<'arena>) -> &'a T {
+        let ptr =
+            ptr_meta::from_raw_parts(arena.resolve(self.offset) as *const (), self.metadata());
+        // SAFETY: Correct by construction; the arena's memory is never moved nor freed while it is borrowed
+        unsafe { &*ptr }
+    }
+
+    /// Promotes the value out of the arena into a standalone SlimmerBox, by cloning it.
+    pub fn to_slimmer_box(self, arena: &SlimmerArena<'arena>) -> SlimmerBox<T, SlimmerMetadata>
+    where
+        T: CloneUnsized,
+    {
+        // SAFETY: The metadata was already checked when allocating in the arena
+        unsafe { SlimmerBox::new_unchecked(self.get(arena)) }
+    }
+
+    /// The offset of the value within the arena.
+    pub fn offset(self) -> u32 {
+        self.offset.get()
+    }
+
+    /// Retrieve access to the stored slimmer metadata value.
+    pub fn slim_metadata(self) -> SlimmerMetadata {
+        self.meta
+    }
+
+    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    pub fn metadata(self) -> <T as Pointee>::Metadata {
+        let aligned_len = self.slim_metadata();
+        aligned_len.expand()
+    }
+}
+
+impl<'arena, T, SlimmerMetadata> Clone for SlimmerArenaBox<'arena, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'arena, T, SlimmerMetadata> Copy for SlimmerArenaBox<'arena, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<'arena, T, SlimmerMetadata> Index<SlimmerArenaBox<'arena, T, SlimmerMetadata>>
+    for SlimmerArena<'arena>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    type Output = T;
+
+    fn index(&self, arena_box: SlimmerArenaBox<'arena, T, SlimmerMetadata>) -> &T {
+        arena_box.get(self)
+    }
+}
+
+impl<'arena, T, SlimmerMetadata> core::fmt::Debug for SlimmerArenaBox<'arena, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    <T as Pointee>::Metadata: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SlimmerArenaBox")
+            .field("offset", &self.offset())
+            .field("metadata", &self.metadata())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<SlimmerArenaBox<str>>(), 8);
+        assert_eq!(core::mem::size_of::<Option<SlimmerArenaBox<[u64]>>>(), 8);
+        assert_eq!(core::mem::size_of::<SlimmerArenaBox<u64, ()>>(), 4);
+    }
+
+    #[test]
+    fn values_survive_new_chunks() {
+        SlimmerArena::scope(|arena| {
+            let boxes: Vec<SlimmerArenaBox<[u64], u16>> = (0..1000u64)
+                .map(|n| arena.alloc_slim(&[n, n * 2, n * 3][..]))
+                .collect();
+            assert!(arena.chunks.borrow().len() > 1);
+            for (n, arena_box) in (0..1000u64).zip(boxes) {
+                assert_eq!(&arena[arena_box], &[n, n * 2, n * 3]);
+                assert_eq!(arena_box.get(arena).as_ptr() as usize % 8, 0);
+            }
+        });
+    }
+
+    #[test]
+    fn sized_empty_and_over_aligned() {
+        #[derive(Clone, Debug, PartialEq)]
+        #[repr(align(64))]
+        struct OverAligned(u8);
+
+        SlimmerArena::scope(|arena| {
+            let _unaligned: SlimmerArenaBox<str> = arena.alloc_slim("x");
+            let over_aligned: SlimmerArenaBox<OverAligned, ()> = arena.alloc_slim(&OverAligned(42));
+            let empty: SlimmerArenaBox<[u64]> = arena.alloc_slim(&[][..]);
+            let unit: SlimmerArenaBox<(), ()> = arena.alloc_slim(&());
+            assert_eq!(arena[over_aligned], OverAligned(42));
+            assert_eq!(&arena[over_aligned] as *const _ as usize % 64, 0);
+            assert!(arena[empty].is_empty());
+            assert_eq!(arena[unit], ());
+        });
+    }
+
+    #[test]
+    fn too_long_and_promotion() {
+        let promoted = SlimmerArena::scope(|arena| {
+            assert!(arena.try_alloc_slim::<[u8], u8>(&[0; 256][..]).is_err());
+            let arena_box: SlimmerArenaBox<[u8], u8> = arena.alloc_slim(&[1, 2, 3][..]);
+            arena_box.to_slimmer_box(arena)
+        });
+        assert_eq!(&*promoted, &[1, 2, 3]);
+    }
+}
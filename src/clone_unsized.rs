@@ -1,38 +1,203 @@
 /// Generalization of Clone which supports dynamically-sized or otherwise unsized types.
 ///
+/// Rather than mutating an existing value, a clone is written directly into uninitialized memory,
+/// which is what is needed when building a freshly allocated (slimmer) box.
+///
 /// Implemented for:
 /// - Normal sized T which implements Clone.
 /// - the dynamicaly-sized slice type `[T]`, as long as T itself implements Clone.
-/// - the tynamically-sized string slice type `str`.
+///   Elements are cloned one by one, and if one of the clones panics, the already-cloned elements are dropped again.
+///   (For `Copy` element types, [`CopyUnsized`] copies all elements with a single memcpy instead.)
+/// - the tynamically-sized string slice type `str`, which is always copied using a single memcpy.
 ///
 /// This trait could easily be implemented for any other dynamically-sized type as well.
-pub trait CloneUnsized {
-    /// Mutates `self` to become a clone of `source`.
+///
+/// # Safety
+///
+/// When `clone_to_uninit` returns normally, the memory at `dest` needs to contain a valid clone of `self`.
+/// When it panics instead, it needs to leave nothing behind at `dest` that would need to be dropped.
+pub unsafe trait CloneUnsized {
+    /// Writes a clone of `self` into the uninitialized memory at `dest`.
+    ///
+    /// Signature purposefully matches the (currently unstable) `core::clone::CloneToUninit`.
+    ///
+    /// # Safety
     ///
-    /// Signature purposefully matches `Clone::clone_from`
-    /// and `std::slice::clone_from_slice()`.
-    fn unsized_clone_from(&mut self, source: &Self);
+    /// `dest` needs to be valid for writes of `core::mem::size_of_val(self)` bytes,
+    /// and properly aligned for `Self`.
+    /// Any value already present at `dest` is overwritten without being dropped.
+    unsafe fn clone_to_uninit(&self, dest: *mut u8);
 }
 
-impl<T> CloneUnsized for [T]
+unsafe impl<T> CloneUnsized for [T]
 where
     T: Clone,
 {
-    fn unsized_clone_from(&mut self, source: &Self) {
-        self.clone_from_slice(source)
+    unsafe fn clone_to_uninit(&self, dest: *mut u8) {
+        let dest = dest as *mut T;
+        let mut guard = PartialSliceGuard {
+            dest,
+            initialized: 0,
+        };
+        for elem in self {
+            // SAFETY: The caller ensures there is room for `self.len()` elements
+            dest.add(guard.initialized).write(elem.clone());
+            guard.initialized += 1;
+        }
+        core::mem::forget(guard);
     }
 }
 
-impl CloneUnsized for str {
-    fn unsized_clone_from(&mut self, source: &Self) {
-        // SAFETY: Cloning valid UTF8 bytes will result in valid UTF8 bytes
-        unsafe { self.as_bytes_mut() }.clone_from_slice(source.as_bytes())
+unsafe impl CloneUnsized for str {
+    unsafe fn clone_to_uninit(&self, dest: *mut u8) {
+        // SAFETY: Copying valid UTF8 bytes will result in valid UTF8 bytes
+        self.copy_to_uninit(dest)
     }
 }
 
 /// Blanket implementation for any sized T that uses the normal Clone.
-impl<T: Clone> CloneUnsized for T {
-    fn unsized_clone_from(&mut self, source: &Self) {
-        *self = source.clone();
+unsafe impl<T: Clone> CloneUnsized for T {
+    unsafe fn clone_to_uninit(&self, dest: *mut u8) {
+        // If `clone` panics, nothing was written yet.
+        (dest as *mut T).write(self.clone());
+    }
+}
+
+/// The `Copy` counterpart of [`CloneUnsized`], for types which can be cloned by copying their bytes.
+///
+/// Implemented for:
+/// - Normal sized T which implements Copy.
+/// - the dynamically-sized slice type `[T]`, as long as T itself implements Copy.
+/// - the dynamically-sized string slice type `str`.
+///
+/// Unlike `clone_to_uninit`, which clones slices element by element,
+/// `copy_to_uninit` always copies the whole value using a single memcpy and never panics.
+/// This is what [`SlimmerBox::new_copied`](crate::SlimmerBox::new_copied) uses.
+///
+/// # Safety
+///
+/// A bytewise copy of a value needs to be a valid clone of it, just like for [`Copy`].
+pub unsafe trait CopyUnsized: CloneUnsized {
+    /// Writes a bytewise copy of `self` into the uninitialized memory at `dest`.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`CloneUnsized::clone_to_uninit`].
+    #[inline]
+    unsafe fn copy_to_uninit(&self, dest: *mut u8) {
+        core::ptr::copy_nonoverlapping(
+            self as *const Self as *const u8,
+            dest,
+            core::mem::size_of_val(self),
+        );
+    }
+}
+
+unsafe impl<T: Copy> CopyUnsized for [T] {}
+
+unsafe impl CopyUnsized for str {}
+
+/// Blanket implementation for any sized T that is Copy.
+unsafe impl<T: Copy> CopyUnsized for T {}
+
+/// Drops the elements of a slice that were written so far,
+/// when creating one of the later elements panics.
+pub(crate) struct PartialSliceGuard<T> {
+    pub(crate) dest: *mut T,
+    pub(crate) initialized: usize,
+}
+
+impl<T> Drop for PartialSliceGuard<T> {
+    fn drop(&mut self) {
+        // SAFETY: The first `initialized` elements were written to and are not used afterwards
+        unsafe {
+            core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+                self.dest,
+                self.initialized,
+            ))
+        }
+    }
+}
+
+/// Calls the wrapped closure when dropped.
+///
+/// Used to clean up a fresh allocation when cloning into it panics;
+/// once the clone succeeded, the guard is disarmed with `core::mem::forget`.
+/// (`SlimmerBox`'s Drop impl instead keeps it armed, to deallocate whether or not dropping the value panics.)
+#[cfg(feature = "alloc")]
+pub(crate) struct OnUnwind<F: FnMut()>(pub(crate) F);
+
+#[cfg(feature = "alloc")]
+impl<F: FnMut()> Drop for OnUnwind<F> {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+    use alloc::string::String;
+    use core::mem::MaybeUninit;
+
+    #[test]
+    fn clone_to_uninit_slice() {
+        let source = [String::from("a"), String::from("b"), String::from("c")];
+        let mut dest: MaybeUninit<[String; 3]> = MaybeUninit::uninit();
+        let dest = unsafe {
+            source[..].clone_to_uninit(dest.as_mut_ptr() as *mut u8);
+            dest.assume_init()
+        };
+        assert_eq!(source, dest);
+
+        let source = [1u32, 2, 3];
+        let mut dest: MaybeUninit<[u32; 3]> = MaybeUninit::uninit();
+        let dest = unsafe {
+            source[..].clone_to_uninit(dest.as_mut_ptr() as *mut u8);
+            dest.assume_init()
+        };
+        assert_eq!(source, dest);
+    }
+
+    #[test]
+    fn copy_to_uninit() {
+        let source = [1u32, 2, 3];
+        let mut dest: MaybeUninit<[u32; 3]> = MaybeUninit::uninit();
+        let dest = unsafe {
+            source[..].copy_to_uninit(dest.as_mut_ptr() as *mut u8);
+            dest.assume_init()
+        };
+        assert_eq!(source, dest);
+
+        let mut dest: MaybeUninit<[u8; 5]> = MaybeUninit::uninit();
+        let dest = unsafe {
+            "hello".copy_to_uninit(dest.as_mut_ptr() as *mut u8);
+            dest.assume_init()
+        };
+        assert_eq!(&dest, b"hello");
+    }
+
+    #[test]
+    fn panicking_clone_drops_partial_clones() {
+        struct PanicOnThird(Rc<()>, usize);
+        impl Clone for PanicOnThird {
+            fn clone(&self) -> Self {
+                if self.1 == 2 {
+                    panic!("third clone panics");
+                }
+                PanicOnThird(self.0.clone(), self.1)
+            }
+        }
+
+        let counter = Rc::new(());
+        let source: [PanicOnThird; 4] = core::array::from_fn(|i| PanicOnThird(counter.clone(), i));
+        let mut dest: MaybeUninit<[PanicOnThird; 4]> = MaybeUninit::uninit();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+            source[..].clone_to_uninit(dest.as_mut_ptr() as *mut u8)
+        }));
+        assert!(result.is_err());
+        // Only the four originals (and `counter` itself) remain
+        assert_eq!(Rc::strong_count(&counter), 5);
     }
 }
@@ -0,0 +1,473 @@
+//! A clone-on-write smart pointer for slices and strings whose 'fat' pointer is 'slimmer'.
+//!
+//! See [`SlimmerCow`] for more information.
+use alloc::boxed::Box;
+use core::{marker::PhantomData, ops::Deref, ptr::NonNull};
+use ptr_meta::Pointee;
+
+use crate::{
+    CloneUnsized, PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox, SlimmerPointee,
+};
+
+/// A clone-on-write smart pointer which either borrows a `&'a T` or owns a [`SlimmerBox<T>`],
+/// for slices and strings.
+///
+/// This is to `Cow<'a, T>` what [`SlimmerBox<T>`] is to `Box<T>`:
+/// A `SlimmerCow<'a, str>` takes up the same 12 bytes as a `SlimmerBox<str>` on 64-bit systems,
+/// rather than the 24 bytes of a `Cow<'a, str>`.
+///
+/// Whether the value is borrowed or owned is stored in the lowest bit of the SlimmerMetadata;
+/// the length is stored in the other bits. This means that the maximum length of a SlimmerCow
+/// is _half_ of the maximum length of a SlimmerBox using the same SlimmerMetadata,
+/// namely `(SlimmerMetadata::MAX - 1) / 2`.
+/// (So for a `SlimmerCow<'a, [T], u32>`, the length must fit in 31 bits,
+/// and a `SlimmerCow<'a, str, Bounded<u16, 1000>>` holds at most 499 bytes.)
+///
+/// ```rust
+/// use slimmer_box::{SlimmerBox, SlimmerCow};
+///
+/// let mut cow: SlimmerCow<str> = SlimmerCow::borrowed("hello");
+/// assert_eq!(core::mem::size_of_val(&cow), 12);
+/// assert!(SlimmerCow::is_borrowed(&cow));
+///
+/// SlimmerCow::to_mut(&mut cow).make_ascii_uppercase();
+/// assert!(!SlimmerCow::is_borrowed(&cow));
+///
+/// let owned: SlimmerBox<str> = SlimmerCow::into_owned(cow);
+/// assert_eq!(&*owned, "HELLO");
+/// ```
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerCow<'a, T, SlimmerMetadata = u32>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    ptr: NonNull<()>,
+    // Stores `(len << 1) | is_owned`
+    tagged_meta: SlimmerMetadata,
+    marker: PhantomData<(&'a T, Box<T>)>,
+}
+
+impl<'a, T, SlimmerMetadata> SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Creates a new SlimmerCow borrowing the given value.
+    ///
+    /// Panics if the value's length is too large to fit in SlimmerMetadata (minus its lowest bit).
+    pub fn borrowed(value: &'a T) -> Self {
+        Self::try_borrowed(value).unwrap()
+    }
+
+    /// Variant of `borrowed` which will return an error if the value's length is too large instead of panicing.
+    pub fn try_borrowed(
+        value: &'a T,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>> {
+        let (ptr, len) = ptr_meta::PtrExt::to_raw_parts(value as *const T);
+        let tagged_meta = Self::tag_metadata(len, false)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(len, ()))?;
+        Ok(Self {
+            // SAFETY: References are never null
+            ptr: unsafe { NonNull::new_unchecked(ptr as *mut ()) },
+            tagged_meta,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates a new SlimmerCow owning the given SlimmerBox.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    ///
+    /// Panics if the value's length is too large to fit in SlimmerMetadata (minus its lowest bit).
+    pub fn owned(boxed: SlimmerBox<T, SlimmerMetadata>) -> Self {
+        Self::try_owned(boxed).unwrap()
+    }
+
+    /// Variant of `owned` which will return an error if the value's length is too large instead of panicing.
+    ///
+    /// On error, the SlimmerBox is handed back inside the error.
+    pub fn try_owned(
+        boxed: SlimmerBox<T, SlimmerMetadata>,
+    ) -> Result<
+        Self,
+        PointerMetadataDoesNotFitError<T, SlimmerMetadata, SlimmerBox<T, SlimmerMetadata>>,
+    > {
+        let len = SlimmerBox::metadata(&boxed);
+        let tagged_meta = match Self::tag_metadata(len, true) {
+            Some(tagged_meta) => tagged_meta,
+            None => return Err(PointerMetadataDoesNotFitError::new(len, boxed)),
+        };
+        let ptr = SlimmerBox::into_raw(boxed) as *mut ();
+        Ok(Self {
+            // SAFETY: SlimmerBox ensures its ptr is never null.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            tagged_meta,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns true if the data is borrowed.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn is_borrowed(this: &Self) -> bool {
+        !SlimmerCow::is_owned(this)
+    }
+
+    /// Returns true if the data is owned.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn is_owned(this: &Self) -> bool {
+        SlimmerCow::untagged_metadata(this) & 1 == 1
+    }
+
+    /// Extracts the owned data, cloning it if it was borrowed.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_owned(this: Self) -> SlimmerBox<T, SlimmerMetadata>
+    where
+        T: CloneUnsized,
+    {
+        if SlimmerCow::is_borrowed(&this) {
+            return SlimmerBox::new(&*this);
+        }
+        let ptr = SlimmerCow::to_raw_ptr(&this);
+        core::mem::forget(this);
+        // SAFETY: The length fits in the tagged metadata, so it also fits in the untagged SlimmerMetadata.
+        // The pointer was obtained from a SlimmerBox with the same SlimmerMetadata.
+        unsafe { SlimmerBox::from_box_unchecked(Box::from_raw(ptr)) }
+    }
+
+    /// Acquires a mutable reference to the owned data, cloning it first if it was borrowed.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_mut(this: &mut Self) -> &mut T
+    where
+        T: CloneUnsized,
+    {
+        if SlimmerCow::is_borrowed(this) {
+            let owned = SlimmerBox::new(&**this);
+            // SAFETY: The borrowed length already fit in the tagged metadata
+            *this = unsafe { Self::try_owned(owned).unwrap_unchecked() };
+        }
+        // SAFETY: We own the data, and are borrowed mutably
+        unsafe { &mut *SlimmerCow::to_raw_ptr(this) }
+    }
+
+    /// Obtains a raw read-only pointer to the contents of this SlimmerCow.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        SlimmerCow::to_raw_ptr(this)
+    }
+
+    /// Returns the length of the contents (that is: the pointer metadata of T).
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> usize {
+        SlimmerCow::untagged_metadata(this) >> 1
+    }
+
+    fn to_raw_ptr(this: &Self) -> *mut T {
+        ptr_meta::from_raw_parts_mut(this.ptr.as_ptr(), SlimmerCow::metadata(this))
+    }
+
+    fn untagged_metadata(this: &Self) -> usize {
+        let tagged_meta = this.tagged_meta;
+        tagged_meta.expand()
+    }
+
+    /// The largest length whose tagged metadata fits both when borrowed and when owned,
+    /// so `to_mut` never runs out of room.
+    const MAX_LEN: usize = SlimmerMetadata::MAX.saturating_sub(1) / 2;
+
+    fn tag_metadata(len: usize, is_owned: bool) -> Option<SlimmerMetadata> {
+        if len > Self::MAX_LEN {
+            return None;
+        }
+        SlimmerMetadata::try_compress((len << 1) | is_owned as usize)
+    }
+}
+
+unsafe impl<'a, T: Send + Sync, SlimmerMetadata: Send> Send for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+unsafe impl<'a, T: Sync, SlimmerMetadata: Sync> Sync for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+impl<'a, T, SlimmerMetadata> Drop for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn drop(&mut self) {
+        if SlimmerCow::is_owned(self) {
+            // SAFETY: Owned data was obtained from a SlimmerBox, whose allocation is compatible with Box
+            core::mem::drop(unsafe { Box::from_raw(SlimmerCow::to_raw_ptr(self)) });
+        }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> From<&'a T> for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Creates a borrowed SlimmerCow.
+    ///
+    /// Panics if the value's length is too large to fit in SlimmerMetadata (minus its lowest bit).
+    fn from(value: &'a T) -> Self {
+        Self::borrowed(value)
+    }
+}
+
+impl<'a, T, SlimmerMetadata> From<SlimmerBox<T, SlimmerMetadata>>
+    for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Creates an owned SlimmerCow.
+    ///
+    /// Panics if the value's length is too large to fit in SlimmerMetadata (minus its lowest bit).
+    fn from(boxed: SlimmerBox<T, SlimmerMetadata>) -> Self {
+        Self::owned(boxed)
+    }
+}
+
+impl<'a, T, SlimmerMetadata> Deref for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Correct by construction
+        unsafe { &*SlimmerCow::to_ptr(self) }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::borrow::Borrow<T> for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> AsRef<T> for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> Clone for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    T: CloneUnsized,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Borrowed data is copied as-is, owned data is cloned into a new allocation.
+    fn clone(&self) -> Self {
+        if SlimmerCow::is_borrowed(self) {
+            return Self {
+                ptr: self.ptr,
+                tagged_meta: self.tagged_meta,
+                marker: PhantomData,
+            };
+        }
+        let owned = SlimmerBox::new(&**self);
+        // SAFETY: The length already fit in the tagged metadata
+        unsafe { Self::try_owned(owned).unwrap_unchecked() }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::fmt::Debug for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::fmt::Display for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: core::fmt::Display,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'a, T: PartialEq, SlimmerMetadata> PartialEq for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<'a, T: Eq, SlimmerMetadata> Eq for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+impl<'a, T: PartialOrd, SlimmerMetadata> PartialOrd for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: Ord, SlimmerMetadata> Ord for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: core::hash::Hash, SlimmerMetadata> core::hash::Hash
+    for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<SlimmerCow<str>>(), 12);
+        assert_eq!(core::mem::size_of::<Option<SlimmerCow<str>>>(), 12);
+        assert_eq!(core::mem::size_of::<SlimmerCow<[u64], u8>>(), 9);
+    }
+
+    #[test]
+    fn borrowed_and_owned() {
+        let text = std::string::String::from("hello");
+        let borrowed: SlimmerCow<str> = SlimmerCow::borrowed(&text);
+        assert!(SlimmerCow::is_borrowed(&borrowed));
+        assert_eq!(SlimmerCow::to_ptr(&borrowed) as *const u8, text.as_ptr());
+
+        let owned: SlimmerCow<str> = SlimmerCow::owned(SlimmerBox::new("hello"));
+        assert!(SlimmerCow::is_owned(&owned));
+        assert_eq!(borrowed, owned);
+
+        let cloned = owned.clone();
+        assert!(SlimmerCow::is_owned(&cloned));
+        assert_ne!(SlimmerCow::to_ptr(&cloned), SlimmerCow::to_ptr(&owned));
+        assert_eq!(&*SlimmerCow::into_owned(cloned), "hello");
+    }
+
+    #[test]
+    fn to_mut_clones_once() {
+        let numbers = [1, 2, 3];
+        let mut cow: SlimmerCow<[u64], u8> = SlimmerCow::borrowed(&numbers[..]);
+        SlimmerCow::to_mut(&mut cow)[0] = 10;
+        let ptr = SlimmerCow::to_ptr(&cow);
+        SlimmerCow::to_mut(&mut cow)[1] = 20;
+        assert_eq!(SlimmerCow::to_ptr(&cow), ptr);
+        assert_eq!(&*cow, &[10, 20, 3]);
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+
+    #[test]
+    fn length_limit_is_halved() {
+        let long = [0u8; 128];
+        assert!(SlimmerCow::<[u8], u8>::try_borrowed(&long[..127]).is_ok());
+        assert!(SlimmerCow::<[u8], u8>::try_borrowed(&long[..]).is_err());
+        let boxed: SlimmerBox<[u8], u8> = SlimmerBox::new(&long[..]);
+        assert!(SlimmerCow::try_owned(boxed).is_err());
+    }
+
+    #[test]
+    fn bounded_length_limit() {
+        use crate::Bounded;
+
+        let long = [0u8; 500];
+        // Borrowed, 500 would fit in the tagged metadata, but once owned it would not
+        assert!(SlimmerCow::<[u8], Bounded<u16, 1000>>::try_borrowed(&long[..499]).is_ok());
+        let error = SlimmerCow::<[u8], Bounded<u16, 1000>>::try_borrowed(&long[..]).unwrap_err();
+        assert_eq!(error.metadata(), 500);
+
+        let mut cow = SlimmerCow::<[u8], Bounded<u16, 1000>>::borrowed(&long[..499]);
+        SlimmerCow::to_mut(&mut cow)[0] = 1;
+        assert!(SlimmerCow::is_owned(&cow));
+        assert_eq!(SlimmerCow::metadata(&cow), 499);
+    }
+}
@@ -0,0 +1,125 @@
+//! Internal helpers for allocations that store a small header directly in front of a (possibly unsized) value.
+//!
+//! The pointer that is handed around always points to the _value_,
+//! so it can be turned back into a `*mut T` without knowing anything about the header.
+//! The header can then always be found at `value_ptr - size_of::<H>()`.
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+use crate::clone_unsized::OnUnwind;
+use crate::CloneUnsized;
+
+/// Reference counts are not allowed to grow beyond this, just like those of `Rc` and `Arc`.
+pub(crate) const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+/// Aborts the process, used when a reference count would overflow.
+///
+/// Without std, this panics while already panicking, which aborts as well.
+#[cold]
+pub(crate) fn abort() -> ! {
+    #[cfg(feature = "std")]
+    std::process::abort();
+    #[cfg(not(feature = "std"))]
+    {
+        struct PanicOnDrop;
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                panic!("reference count overflow");
+            }
+        }
+        let _guard = PanicOnDrop;
+        panic!("reference count overflow");
+    }
+}
+
+/// Computes the layout of an allocation containing a `H` directly followed by a value with layout `value_layout`,
+/// together with the offset of the value from the start of the allocation.
+///
+/// The header is stored right in front of the value (padding, if any, comes _before_ the header).
+/// Since `size_of::<H>()` is always a multiple of `align_of::<H>()`, the header is always properly aligned.
+pub(crate) fn layout<H>(value_layout: Layout) -> (Layout, usize) {
+    let header_layout = Layout::new::<H>();
+    let align = core::cmp::max(header_layout.align(), value_layout.align());
+    // NOTE: value_layout.align() is always a power of two.
+    let offset = (header_layout.size() + value_layout.align() - 1) & !(value_layout.align() - 1);
+    let size = offset
+        .checked_add(value_layout.size())
+        .expect("allocation size overflow");
+    let layout = Layout::from_size_align(size, align).expect("allocation size overflow");
+    (layout, offset)
+}
+
+/// Allocates room for a `H` followed by a value with layout `value_layout`.
+///
+/// Returns a pointer to where the value should be written.
+/// Neither the header nor the value are initialized.
+///
+/// # Safety
+/// `H` must not be zero-sized.
+pub(crate) unsafe fn alloc<H>(value_layout: Layout) -> NonNull<()> {
+    try_alloc::<H>(value_layout).unwrap_or_else(|layout| alloc::alloc::handle_alloc_error(layout))
+}
+
+/// Variant of [`alloc`] which returns the layout of the failed allocation rather than calling `handle_alloc_error`.
+///
+/// # Safety
+/// `H` must not be zero-sized.
+pub(crate) unsafe fn try_alloc<H>(value_layout: Layout) -> Result<NonNull<()>, Layout> {
+    let (layout, offset) = layout::<H>(value_layout);
+    let alloc_ptr = alloc::alloc::alloc(layout);
+    if alloc_ptr.is_null() {
+        return Err(layout);
+    }
+    Ok(NonNull::new_unchecked(alloc_ptr.add(offset) as *mut ()))
+}
+
+/// Allocates room for a `H` followed by a clone of `value`, and clones `value` into it.
+///
+/// Returns a pointer to the cloned value,
+/// or the layout of the failed allocation if the allocator returned null.
+/// The header is not initialized.
+/// If cloning panics, the allocation is freed again.
+///
+/// # Safety
+/// `H` must not be zero-sized.
+pub(crate) unsafe fn try_alloc_clone<H, T>(value: &T) -> Result<NonNull<()>, Layout>
+where
+    T: ?Sized + CloneUnsized,
+{
+    let value_layout = Layout::for_value(value);
+    let ptr = try_alloc::<H>(value_layout)?;
+    let guard = OnUnwind(|| dealloc::<H>(ptr.as_ptr(), value_layout));
+    value.clone_to_uninit(ptr.as_ptr() as *mut u8);
+    core::mem::forget(guard);
+    Ok(ptr)
+}
+
+/// Variant of [`try_alloc_clone`] which calls `handle_alloc_error` if the allocation fails.
+///
+/// # Safety
+/// `H` must not be zero-sized.
+pub(crate) unsafe fn alloc_clone<H, T>(value: &T) -> NonNull<()>
+where
+    T: ?Sized + CloneUnsized,
+{
+    try_alloc_clone::<H, T>(value).unwrap_or_else(|layout| alloc::alloc::handle_alloc_error(layout))
+}
+
+/// Returns a pointer to the header belonging to the value at `value_ptr`.
+///
+/// # Safety
+/// `value_ptr` needs to have been returned by [`alloc::<H>`].
+pub(crate) unsafe fn header<H>(value_ptr: *mut ()) -> *mut H {
+    (value_ptr as *mut u8).sub(core::mem::size_of::<H>()) as *mut H
+}
+
+/// Deallocates the allocation containing the header and the value at `value_ptr`.
+///
+/// The header nor the value are dropped.
+///
+/// # Safety
+/// `value_ptr` needs to have been returned by [`alloc::<H>`], called with the same `value_layout`.
+pub(crate) unsafe fn dealloc<H>(value_ptr: *mut (), value_layout: Layout) {
+    let (layout, offset) = layout::<H>(value_layout);
+    alloc::alloc::dealloc((value_ptr as *mut u8).sub(offset), layout)
+}
@@ -0,0 +1,475 @@
+//! A box for slices and strings which stores short lengths inline and rare long lengths inside the heap allocation.
+//!
+//! See [`HybridSlimmerBox<T>`] for more information.
+use alloc::boxed::Box;
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+use ptr_meta::Pointee;
+
+use crate::{header, CloneUnsized, SlimMetadata, SlimmerBox, SlimmerPointee};
+
+/// A combination of [`SlimmerBox<T>`] and [`ThinSlimmerBox<T>`](crate::ThinSlimmerBox) for slices and strings,
+/// which never fails to be constructed but is as small as a `SlimmerBox<T, SlimmerMetadata>`.
+///
+/// Lengths smaller than `SlimmerMetadata::MAX` are stored inline, right next to the pointer, just like in a SlimmerBox.
+/// The inline value `SlimmerMetadata::MAX` is used as a sentinel instead,
+/// meaning that the real length is stored in a small header inside the heap allocation, right in front of the value.
+///
+/// ```rust
+/// use slimmer_box::HybridSlimmerBox;
+///
+/// let short: HybridSlimmerBox<str, u8> = HybridSlimmerBox::new("hello, world!");
+/// assert_eq!(core::mem::size_of_val(&short), 9);
+/// assert!(!HybridSlimmerBox::has_header(&short));
+///
+/// let long_str = "x".repeat(1000);
+/// let long: HybridSlimmerBox<str, u8> = HybridSlimmerBox::new(&*long_str);
+/// assert!(HybridSlimmerBox::has_header(&long));
+/// assert_eq!(&*long, &*long_str);
+/// ```
+///
+/// This is a good choice when nearly all values are short (like identifiers),
+/// but the rare long value should still work rather than panic:
+/// Short values pay nothing extra, and only long values pay for the header and the extra memory access to read the length.
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct HybridSlimmerBox<T, SlimmerMetadata = u8>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    ptr: NonNull<()>,
+    meta: SlimmerMetadata,
+    marker: PhantomData<T>,
+}
+
+impl<T, SlimmerMetadata> HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Creates a new HybridSlimmerBox from the given slice or string.
+    ///
+    /// This involves cloning the slice (which will clone all elements one by one)
+    /// and as such only works for types whose contents are cloneable.
+    /// Otherwise, use `from_box`.
+    pub fn new(value: &T) -> Self
+    where
+        T: CloneUnsized,
+    {
+        let meta = ptr_meta::metadata(value);
+        match Self::try_inline(meta) {
+            Some(slim_meta) => {
+                // SAFETY: The metadata was checked to fit above
+                let boxed: SlimmerBox<T, SlimmerMetadata> =
+                    unsafe { SlimmerBox::new_unchecked(value) };
+                Self::from_raw_parts(SlimmerBox::into_raw(boxed) as *mut (), slim_meta)
+            }
+            None => {
+                // SAFETY: usize is not zero-sized
+                let ptr = unsafe { header::alloc_clone::<usize, T>(value) };
+                // SAFETY: The header was allocated but not yet initialized
+                unsafe { header::header::<usize>(ptr.as_ptr()).write(meta) };
+                Self::from_raw_parts(ptr.as_ptr(), Self::sentinel())
+            }
+        }
+    }
+
+    /// Turns a Box into a HybridSlimmerBox.
+    ///
+    /// If the length fits inline, the allocation of the Box is re-used.
+    /// Otherwise, the value is moved into a new allocation which also contains the length.
+    pub fn from_box(boxed: Box<T>) -> Self {
+        let meta = ptr_meta::metadata(&*boxed);
+        if let Some(slim_meta) = Self::try_inline(meta) {
+            return Self::from_raw_parts(Box::into_raw(boxed) as *mut (), slim_meta);
+        }
+
+        let value_layout = Layout::for_value(&*boxed);
+        let box_ptr = Box::into_raw(boxed);
+        unsafe {
+            // SAFETY: usize is not zero-sized
+            let ptr = header::alloc::<usize>(value_layout);
+            header::header::<usize>(ptr.as_ptr()).write(meta);
+            // SAFETY: The value is moved (rather than cloned) into the new allocation,
+            // after which the old allocation is freed without dropping the value.
+            core::ptr::copy_nonoverlapping(
+                box_ptr as *const u8,
+                ptr.as_ptr() as *mut u8,
+                value_layout.size(),
+            );
+            if value_layout.size() != 0 {
+                alloc::alloc::dealloc(box_ptr as *mut u8, value_layout);
+            }
+            Self::from_raw_parts(ptr.as_ptr(), Self::sentinel())
+        }
+    }
+
+    /// Turns a HybridSlimmerBox into a Box.
+    ///
+    /// If the length was stored inline, this re-uses the allocation.
+    /// Otherwise, the value is moved into a new allocation which no longer contains the length.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_box(this: Self) -> Box<T> {
+        let meta = HybridSlimmerBox::metadata(&this);
+        let ptr = this.ptr;
+        if !HybridSlimmerBox::has_header(&this) {
+            core::mem::forget(this);
+            // SAFETY: Inline values live in an allocation with the layout Box expects.
+            return unsafe { Box::from_raw(ptr_meta::from_raw_parts_mut(ptr.as_ptr(), meta)) };
+        }
+
+        let value_layout = Layout::for_value(&*this);
+        let box_ptr = if value_layout.size() == 0 {
+            value_layout.align() as *mut u8
+        } else {
+            let box_ptr = unsafe { alloc::alloc::alloc(value_layout) };
+            if box_ptr.is_null() {
+                alloc::alloc::handle_alloc_error(value_layout)
+            }
+            box_ptr
+        };
+        unsafe {
+            core::ptr::copy_nonoverlapping(ptr.as_ptr() as *const u8, box_ptr, value_layout.size());
+            header::dealloc::<usize>(ptr.as_ptr(), value_layout);
+        }
+        core::mem::forget(this);
+        // SAFETY: The value was moved into an allocation with the layout Box expects.
+        unsafe { Box::from_raw(ptr_meta::from_raw_parts_mut(box_ptr as *mut (), meta)) }
+    }
+
+    /// Returns true if the length is too large to be stored inline,
+    /// and is therefore stored in a header inside the heap allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn has_header(this: &Self) -> bool {
+        let meta = this.meta;
+        meta.expand() == SlimmerMetadata::MAX
+    }
+
+    /// Obtains a raw read-only (non-owned) pointer view of the contents of this HybridSlimmerBox.
+    ///
+    /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        let ptr = this.ptr;
+        ptr_meta::from_raw_parts(ptr.as_ptr(), HybridSlimmerBox::metadata(this))
+    }
+
+    /// Returns the metadata (the length), reading it from the heap allocation if it was not stored inline.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> usize {
+        if HybridSlimmerBox::has_header(this) {
+            let ptr = this.ptr;
+            // SAFETY: The header is written on construction and lives as long as the HybridSlimmerBox
+            unsafe { *header::header::<usize>(ptr.as_ptr()) }
+        } else {
+            let meta = this.meta;
+            meta.expand()
+        }
+    }
+
+    fn from_raw_parts(ptr: *mut (), meta: SlimmerMetadata) -> Self {
+        Self {
+            // SAFETY: Only called with pointers obtained from a Box, SlimmerBox or `header::alloc`
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            meta,
+            marker: PhantomData,
+        }
+    }
+
+    /// Compresses `meta`, unless it is too large to be stored inline (which includes the sentinel itself).
+    fn try_inline(meta: usize) -> Option<SlimmerMetadata> {
+        if meta < SlimmerMetadata::MAX {
+            SlimmerMetadata::try_compress(meta)
+        } else {
+            None
+        }
+    }
+
+    fn sentinel() -> SlimmerMetadata {
+        SlimmerMetadata::try_compress(SlimmerMetadata::MAX)
+            .expect("SlimMetadata::MAX should always be compressible")
+    }
+}
+
+unsafe impl<T: Send, SlimmerMetadata> Send for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+unsafe impl<T: Sync, SlimmerMetadata> Sync for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+impl<T, SlimmerMetadata> Drop for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn drop(&mut self) {
+        let ptr = HybridSlimmerBox::to_ptr(self) as *mut T;
+        unsafe {
+            let value_layout = Layout::for_value(&*ptr);
+            core::ptr::drop_in_place(ptr);
+            if HybridSlimmerBox::has_header(self) {
+                header::dealloc::<usize>(ptr as *mut (), value_layout);
+            } else if value_layout.size() != 0 {
+                alloc::alloc::dealloc(ptr as *mut u8, value_layout);
+            }
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> Deref for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Correct by construction
+        unsafe { &*HybridSlimmerBox::to_ptr(self) }
+    }
+}
+
+impl<T, SlimmerMetadata> DerefMut for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: Correct by construction
+        unsafe { &mut *(HybridSlimmerBox::to_ptr(self) as *mut T) }
+    }
+}
+
+impl<T, SlimmerMetadata> core::borrow::Borrow<T> for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> core::borrow::BorrowMut<T> for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> AsRef<T> for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> AsMut<T> for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> Unpin for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+impl<T, SlimmerMetadata> Clone for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: CloneUnsized,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn clone(&self) -> Self {
+        HybridSlimmerBox::new(self.deref())
+    }
+}
+
+impl<T, SlimmerMetadata> core::fmt::Debug for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: PartialEq, SlimmerMetadata> PartialEq for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<T: PartialOrd, SlimmerMetadata> PartialOrd for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, SlimmerMetadata> Ord for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: Eq, SlimmerMetadata> Eq for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+impl<T: core::hash::Hash, SlimmerMetadata> core::hash::Hash for HybridSlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<HybridSlimmerBox<str, u8>>(), 9);
+        assert_eq!(core::mem::size_of::<HybridSlimmerBox<[u64], u16>>(), 10);
+        assert_eq!(core::mem::size_of::<Option<HybridSlimmerBox<str>>>(), 9);
+    }
+
+    #[test]
+    fn inline_and_header() {
+        let exactly_below: String = "x".repeat(254);
+        let boxed: HybridSlimmerBox<str> = HybridSlimmerBox::new(&*exactly_below);
+        assert!(!HybridSlimmerBox::has_header(&boxed));
+        assert_eq!(&*boxed, &*exactly_below);
+
+        // The sentinel value itself cannot be stored inline
+        let sentinel: String = "x".repeat(255);
+        let boxed: HybridSlimmerBox<str> = HybridSlimmerBox::new(&*sentinel);
+        assert!(HybridSlimmerBox::has_header(&boxed));
+        assert_eq!(HybridSlimmerBox::metadata(&boxed), 255);
+        assert_eq!(&*boxed, &*sentinel);
+
+        let long: String = "y".repeat(100_000);
+        let mut boxed: HybridSlimmerBox<str> =
+            HybridSlimmerBox::from_box(long.clone().into_boxed_str());
+        assert!(HybridSlimmerBox::has_header(&boxed));
+        boxed.make_ascii_uppercase();
+        assert_eq!(&*boxed, &*long.to_uppercase());
+        assert_eq!(boxed.clone(), boxed);
+    }
+
+    #[test]
+    fn roundtrip_box() {
+        let short: HybridSlimmerBox<[String]> = HybridSlimmerBox::new(&[String::from("a")][..]);
+        let boxed = HybridSlimmerBox::into_box(short);
+        assert_eq!(&*boxed, &[String::from("a")]);
+        let short = HybridSlimmerBox::<_, u8>::from_box(boxed);
+        assert!(!HybridSlimmerBox::has_header(&short));
+
+        let many = alloc::vec![String::from("b"); 300].into_boxed_slice();
+        let long = HybridSlimmerBox::<_, u8>::from_box(many.clone());
+        assert!(HybridSlimmerBox::has_header(&long));
+        assert_eq!(HybridSlimmerBox::into_box(long), many);
+    }
+
+    #[test]
+    fn empty_and_zero_sized() {
+        let empty: HybridSlimmerBox<[u64]> = HybridSlimmerBox::new(&[][..]);
+        assert_eq!(&*empty, &[] as &[u64]);
+
+        let zsts: HybridSlimmerBox<[()]> = HybridSlimmerBox::new(&[(); 1000][..]);
+        assert!(HybridSlimmerBox::has_header(&zsts));
+        assert_eq!(zsts.len(), 1000);
+        assert_eq!(HybridSlimmerBox::into_box(zsts).len(), 1000);
+    }
+}
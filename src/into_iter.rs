@@ -0,0 +1,266 @@
+//! An owning iterator over the elements of a `SlimmerBox<[T]>`.
+//!
+//! See [`IntoIter<T>`] for more information.
+use core::{alloc::Layout, mem::ManuallyDrop, ptr::NonNull};
+
+use allocator_api2::alloc::{Allocator, Global};
+use ptr_meta::Pointee;
+
+use crate::{DefaultSlimmerMetadata, SlimMetadata, SlimmerBox, SlimmerPointee};
+
+/// An iterator that moves the elements out of a [`SlimmerBox<[T]>`](SlimmerBox).
+///
+/// Created by the `into_iter` method on SlimmerBox (provided by the [`IntoIterator`] trait).
+///
+/// The elements are read straight out of the SlimmerBox's allocation, which is re-used rather than copied.
+/// When the iterator is dropped, the elements that were not yet yielded are dropped and the allocation is freed.
+///
+/// ```rust
+/// use slimmer_box::SlimmerBox;
+/// use std::string::String;
+///
+/// let boxed: SlimmerBox<[String]> = SlimmerBox::from_fn(3, |index| index.to_string());
+/// let mut iter = boxed.into_iter();
+/// assert_eq!(iter.next_back(), Some(String::from("2")));
+/// assert_eq!(iter.as_slice(), &["0", "1"]);
+/// assert_eq!(iter.len(), 2);
+/// ```
+pub struct IntoIter<T, SlimmerMetadata = <[T] as DefaultSlimmerMetadata>::Metadata, A = Global>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    boxed: ManuallyDrop<SlimmerBox<[T], SlimmerMetadata, A>>,
+    front: usize,
+    back: usize,
+}
+
+impl<T, SlimmerMetadata, A> IntoIter<T, SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    /// Returns the remaining (not yet yielded) elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: The elements in front..back are initialized and not yet yielded
+        unsafe { core::slice::from_raw_parts(self.ptr().add(self.front), self.back - self.front) }
+    }
+
+    /// Returns the remaining (not yet yielded) elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: The elements in front..back are initialized and not yet yielded
+        unsafe {
+            core::slice::from_raw_parts_mut(self.ptr().add(self.front), self.back - self.front)
+        }
+    }
+
+    fn ptr(&self) -> *mut T {
+        SlimmerBox::to_ptr(&self.boxed) as *mut T
+    }
+}
+
+impl<T, SlimmerMetadata, A> IntoIterator for SlimmerBox<[T], SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, SlimmerMetadata, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let len = SlimmerBox::metadata(&self);
+        IntoIter {
+            boxed: ManuallyDrop::new(self),
+            front: 0,
+            back: len,
+        }
+    }
+}
+
+impl<'a, T, SlimmerMetadata, A> IntoIterator for &'a SlimmerBox<[T], SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, SlimmerMetadata, A> IntoIterator for &'a mut SlimmerBox<[T], SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, SlimmerMetadata, A> Iterator for IntoIter<T, SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        // SAFETY: The element is initialized and will not be yielded or dropped again
+        let element = unsafe { self.ptr().add(self.front).read() };
+        self.front += 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T, SlimmerMetadata, A> DoubleEndedIterator for IntoIter<T, SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        // SAFETY: The element is initialized and will not be yielded or dropped again
+        Some(unsafe { self.ptr().add(self.back).read() })
+    }
+}
+
+impl<T, SlimmerMetadata, A> ExactSizeIterator for IntoIter<T, SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+}
+
+impl<T, SlimmerMetadata, A> core::iter::FusedIterator for IntoIter<T, SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+}
+
+impl<T, SlimmerMetadata> Clone for IntoIter<T, SlimmerMetadata>
+where
+    T: Clone,
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn clone(&self) -> Self {
+        // SAFETY: The remaining elements were taken from a SlimmerBox of at least this length
+        unsafe { SlimmerBox::new_unchecked(self.as_slice()) }.into_iter()
+    }
+}
+
+impl<T, SlimmerMetadata, A> core::fmt::Debug for IntoIter<T, SlimmerMetadata, A>
+where
+    T: core::fmt::Debug,
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, SlimmerMetadata, A> Drop for IntoIter<T, SlimmerMetadata, A>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        /// Frees the allocation, even if dropping one of the remaining elements panics.
+        struct Dealloc<'r, T, SlimmerMetadata, A>(&'r mut IntoIter<T, SlimmerMetadata, A>)
+        where
+            [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+            SlimmerMetadata: SlimMetadata<usize>,
+            A: Allocator;
+
+        impl<'r, T, SlimmerMetadata, A> Drop for Dealloc<'r, T, SlimmerMetadata, A>
+        where
+            [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+            SlimmerMetadata: SlimMetadata<usize>,
+            A: Allocator,
+        {
+            fn drop(&mut self) {
+                let len = SlimmerBox::metadata(&self.0.boxed);
+                // SAFETY: The SlimmerBox is not used again after being taken out
+                let boxed = unsafe { ManuallyDrop::take(&mut self.0.boxed) };
+                let (ptr, alloc) = SlimmerBox::into_raw_with_allocator(boxed);
+                // SAFETY: This is the layout the slice was allocated with
+                unsafe {
+                    let layout = Layout::array::<T>(len).unwrap_unchecked();
+                    if layout.size() != 0 {
+                        alloc.deallocate(NonNull::new_unchecked(ptr as *mut u8), layout);
+                    }
+                }
+            }
+        }
+
+        let remaining: *mut [T] = self.as_mut_slice();
+        let _guard = Dealloc(self);
+        // SAFETY: These elements were not yet yielded
+        unsafe { core::ptr::drop_in_place(remaining) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn into_iter() {
+        let boxed: SlimmerBox<[String], u8> = SlimmerBox::from_fn(5, |index| index.to_string());
+        let mut iter = boxed.into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next().as_deref(), Some("0"));
+        assert_eq!(iter.next_back().as_deref(), Some("4"));
+        assert_eq!(iter.as_slice(), &["1", "2", "3"]);
+        assert_eq!(iter.clone().collect::<Vec<_>>(), &["1", "2", "3"]);
+        // The remaining elements are dropped together with the iterator
+        drop(iter);
+
+        let empty: SlimmerBox<[String]> = SlimmerBox::default();
+        assert_eq!(empty.into_iter().next(), None);
+
+        let zsts: SlimmerBox<[()]> = SlimmerBox::repeat((), 10);
+        assert_eq!(zsts.into_iter().rev().count(), 10);
+    }
+
+    #[test]
+    fn by_reference() {
+        let mut boxed: SlimmerBox<[u64]> = SlimmerBox::from_array([1, 2, 3]);
+        for elem in &mut boxed {
+            *elem *= 2;
+        }
+        assert_eq!((&boxed).into_iter().sum::<u64>(), 12);
+    }
+}
@@ -5,45 +5,125 @@
 //! [`SlimmerBox<T>`] is the main type exposed by this crate. Detailed documentation can be found there.
 //!
 //! Other, less frequently useful types:
+//! - [`SlimmerRc<T>`] and [`SlimmerArc<T>`]: reference-counted alternatives to `Rc<T>` and `Arc<T>` with the same slimmer 'fat' pointer. (Their [`Weak`](rc::Weak) counterparts live in the [`rc`] and [`sync`] modules.)
+//! - [`ThinSlimmerBox<T>`]: a box for slices and strings which stores the length inside the heap allocation, making it exactly one word in size.
+//! - [`HybridSlimmerBox<T>`]: a box for slices and strings which stores short lengths inline like a SlimmerBox, and lengths that do not fit in a header inside the heap allocation, so construction never fails.
+//! - [`PackedSlimmerBox<T>`]: a box for slices and strings which stores a 16-bit length in the unused high bits of the pointer, making it exactly one word in size. (Only available on 64-bit targets.)
+//! - [`TaggedSlimmerBox<T>`]: a SlimmerBox which stores a small tag in the (always zero) low bits of its pointer, without growing in size.
+//! - [`SlimmerVec<T>`]: a growable vector whose length and capacity are stored in a (smaller) `SlimmerMetadata`, which can be turned into a `SlimmerBox<[T]>` without copying.
+//! - [`SlimmerString`]: the same for UTF-8 strings, which can be turned into a `SlimmerBox<str>` without copying.
+//! - [`SlimmerCow<T>`]: a clone-on-write alternative to `Cow<'a, T>` for slices and strings, which is as slim as a `SlimmerBox<T>`.
+//! - [`SlimmerArenaBox<T>`]: a value living in a [`SlimmerArena`], stored as a 32-bit offset plus a slimmer metadata, making it only 8 bytes in size.
+//! - [`SlimmerRef<T>`] and [`SlimmerMut<T>`]: borrowed alternatives to `&T` and `&mut T` with the same slimmer 'fat' pointer. These never allocate.
 //! - [`CloneUnsized`]: a helper trait to also allow unsized types whose _contents_ are clone. (like [`[T]`](slice) and [`str`]) to be cloned around.
+//! - [`CopyUnsized`]: its counterpart for unsized types whose contents are `Copy`, which are copied using a single memcpy.
 //! - [`SlimmerPointee`]: a helper trait implemented for all sized and unsized types for which a 'fat' pointer might be made slimmer.
+//! - [`DefaultSlimmerMetadata`]: the SlimmerMetadata a pointee uses when none is given, like `u32` for slices and strings in `SlimmerBox<[T]>` and `SlimmerBox<str>`.
+//! - [`SlimMetadata`]: the trait describing a compact representation of pointer metadata, implemented for `()` and the unsigned integers. You can implement it for your own types as well.
 //!
 //! # Feature flags
 //!
-//! - `"std"`. Enabled by default. Disable the default features to use the crate in no_std environments. `slimmer_box` *does* require the `alloc` crate to be available.
+//! - `"std"`. Enabled by default. Disable the default features to use the crate in no_std environments. Implies `"alloc"`.
+//! - `"alloc"`. Enabled by `"std"`. Everything that allocates (SlimmerBox, all other owning types and the rkyv and serde integrations) requires the `alloc` crate and thus this feature. Without it, only [`SlimmerRef`], [`SlimmerMut`] and the metadata traits are available.
 //! - `"rkyv"`. Enable support for the [rkyv](https://crates.io/crates/rkyv) zero-copy serialisation/deserialisation library, which is a very good match for this crate!
 //! - `"serde"`. Enable support for the [serde](https://crates.io/crates/serde) serialisation/deserialisation library.
 //!
 //!
 //! # MSRV
-//! The minimum supported Rust version of `slimmer_box` is 1.58.1.
+//! The minimum supported Rust version of `slimmer_box` is 1.63.
 
 // Enable std in tests for easier debugging
 #[cfg(any(feature = "std", test))]
 #[macro_use]
 extern crate std;
 
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
 use core::{
-    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
     ops::{Deref, DerefMut},
     ptr::NonNull,
 };
+#[cfg(feature = "alloc")]
+use allocator_api2::alloc::{Allocator, Global};
+#[cfg(feature = "alloc")]
+use crate::clone_unsized::{OnUnwind, PartialSliceGuard};
 use ptr_meta::Pointee;
 
+#[cfg(feature = "alloc")]
+mod header;
+#[cfg(feature = "alloc")]
+mod resize;
+
+#[cfg(feature = "alloc")]
+pub mod arena;
 pub mod clone_unsized;
+#[cfg(feature = "alloc")]
+pub mod cow;
+#[cfg(feature = "alloc")]
+pub mod hybrid;
+#[cfg(feature = "alloc")]
+pub mod into_iter;
+#[cfg(all(feature = "alloc", target_pointer_width = "64"))]
+pub mod packed;
+#[cfg(feature = "alloc")]
+pub mod rc;
+pub mod slim_metadata;
 pub mod slim_pointee;
-pub use crate::clone_unsized::CloneUnsized;
-pub use crate::slim_pointee::SlimmerPointee;
+pub mod slim_ref;
+#[cfg(feature = "alloc")]
+pub mod string;
+#[cfg(feature = "alloc")]
+pub mod sync;
+#[cfg(feature = "alloc")]
+pub mod tagged;
+#[cfg(feature = "alloc")]
+pub mod thin;
+#[cfg(feature = "alloc")]
+pub mod vec;
+#[cfg(feature = "alloc")]
+pub use crate::arena::{SlimmerArena, SlimmerArenaBox};
+pub use crate::clone_unsized::{CloneUnsized, CopyUnsized};
+#[cfg(feature = "alloc")]
+pub use crate::cow::SlimmerCow;
+#[cfg(feature = "alloc")]
+pub use crate::hybrid::HybridSlimmerBox;
+#[cfg(all(feature = "alloc", target_pointer_width = "64"))]
+pub use crate::packed::PackedSlimmerBox;
+#[cfg(feature = "alloc")]
+pub use crate::rc::SlimmerRc;
+pub use crate::slim_metadata::{Bounded, SlimMetadata, U24, U40, U48};
+pub use crate::slim_pointee::{DefaultSlimmerMetadata, SlimmerPointee};
+pub use crate::slim_ref::{SlimmerMut, SlimmerRef};
+#[cfg(feature = "alloc")]
+pub use crate::string::SlimmerString;
+#[cfg(feature = "alloc")]
+pub use crate::sync::SlimmerArc;
+#[cfg(feature = "alloc")]
+pub use crate::tagged::TaggedSlimmerBox;
+#[cfg(feature = "alloc")]
+pub use crate::thin::ThinSlimmerBox;
+#[cfg(feature = "alloc")]
+pub use crate::vec::SlimmerVec;
+#[cfg(feature = "alloc")]
+pub use allocator_api2;
 
-#[cfg(feature = "rkyv")]
+#[cfg(all(feature = "alloc", feature = "rkyv"))]
 pub mod rkyv;
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "alloc", feature = "serde"))]
 pub mod serde;
 
+#[cfg(feature = "alloc")]
 /// A packed alternative to [`Box<T>`](alloc::boxed::Box) whose 'fat' pointer is 'slimmer'.
 ///
 /// A normal `Box<[T]>` is an owned 'fat pointer' that contains both the 'raw' pointer to memory
@@ -123,7 +203,10 @@ pub mod serde;
 ///
 /// # Different sizes
 ///
-/// SlimmerBox<T, u32> is the most common version, and therefore u32 is the default SlimmerMetadata to use.
+/// When no SlimmerMetadata is given, each pointee picks its own default
+/// (through [`DefaultSlimmerMetadata`]):
+/// SlimmerBox<\[T\], u32> and SlimmerBox<str, u32> for slices and strings, as that is the most common version,
+/// and SlimmerBox<T, ()> for sized types, so a plain `SlimmerBox<u64>` just works.
 /// But it is possible to use another variant, if you are sure that your data will be even shorter.
 ///
 /// - SlimmerMetadata = [`()`](unit) is used for sized types. In this case a SlimmerBox will only contain the normal pointer and be exactly 1 word size, just like a normal [Box](alloc::boxed::Box).
@@ -134,10 +217,14 @@ pub mod serde;
 /// | ()              | -                    | 4 bytes                | 8 bytes                | Used for normal sized types. Identical in size to a normal `Box<T>` in this case. |
 /// | u8              | 255                  | 5 bytes                | 9 bytes                |                                                                                 |
 /// | u16             | 65535                | 6 bytes                | 10 bytes               | Identical to `Box<DST>` on 16-bit systems                                         |
+/// | U24             | 16777215             | 7 bytes                | 11 bytes               |                                                                                 |
 /// | u32             | 4294967295           | 8 bytes (2 words)      | 12 bytes               | Identical to `Box<DST>` on 32-bit systems                                         |
+/// | U40             | 1099511627775        | 9 bytes                | 13 bytes               |                                                                                 |
+/// | U48             | 281474976710655      | 10 bytes               | 14 bytes               |                                                                                 |
 /// | u64             | 18446744073709551615 | -²                     | 16 bytes (2 words)     | Identical to `Box<DST>` on 64-bit systems                                         |
 ///
 /// - ¹ Max DST length is in bytes for `str` and in the number of elements for slices.
+///   [U24], [U40] and [U48] are odd-width integers stored as byte arrays; on 32-bit systems their max length is capped at `usize::MAX`.
 ///
 /// # Niche optimization
 ///
@@ -152,6 +239,9 @@ pub mod serde;
 /// (This is assuming rkyv's feature `size_32` is used which is the default.
 /// Changing it to `size_64` is rarely useful for the same reason as the rant about lengths above.)
 ///
+/// Just like `from_box`, deserializing panics if an archived value is too large for the SlimmerMetadata.
+/// Use `slimmer_box::rkyv::try_deserialize` to get an error instead.
+///
 /// # Limitations
 ///
 /// You can _not_ use a SlimmerBox to store a trait object.
@@ -159,7 +249,7 @@ pub mod serde;
 ///
 /// # `no_std` support
 ///
-/// SlimmerBox works perfectly fine in `no_std` environments, as long as the `alloc` crate is available.
+/// SlimmerBox works perfectly fine in `no_std` environments, as long as the `alloc` crate is available: disable the default features and enable the `"alloc"` feature.
 ///
 /// (The only thing that is missing in no_std environments are implementations for [SlimmerPointee](SlimmerPointee) of `std::ffi::OsStr` and `std::ffi::CStr`, neither of which exists when `std` is disabled.)
 ///
@@ -228,32 +318,90 @@ pub mod serde;
 /// let again_boxed = SlimmerBox::into_box(slimmer_box);
 /// ```
 ///
+/// # Custom allocators
+///
+/// Like `Box<T, A>`, a SlimmerBox can use a custom [`Allocator`](allocator_api2::alloc::Allocator)
+/// (from the [allocator-api2](https://crates.io/crates/allocator-api2) crate, which is re-exported), using `new_in`, `try_new_in`, `from_box_in` and `into_box_in`.
+/// By default the [`Global`](allocator_api2::alloc::Global) allocator is used.
+/// Zero-sized allocators (like `Global`) do not increase the size of the SlimmerBox.
+///
 #[repr(packed)]
-pub struct SlimmerBox<T, SlimmerMetadata = u32>
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerBox<T, SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata, A = Global>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
 {
     ptr: core::ptr::NonNull<()>,
     meta: SlimmerMetadata,
+    // NOTE: Wrapped in ManuallyDrop as we need to move it out of the (packed) struct on drop.
+    alloc: ManuallyDrop<A>,
+    marker: PhantomData<T>,
+}
+
+/// The error returned when the pointer metadata of a value (like the length of a slice) is too large to fit in `SlimmerMetadata`.
+///
+/// Fallible constructors that consume their input (like [`SlimmerBox::try_from_box`]) hand it back inside this error,
+/// so it is not lost and the caller can fall back to another representation.
+/// For those that do not consume their input, `Input` is `()`.
+pub struct PointerMetadataDoesNotFitError<T, SlimmerMetadata, Input = ()>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    metadata: <T as Pointee>::Metadata,
+    input: Input,
     marker: PhantomData<T>,
+    slimmer_marker: PhantomData<SlimmerMetadata>,
 }
 
-pub struct PointerMetadataDoesNotFitError<T, SlimmerMetadata>(
-    PhantomData<T>,
-    PhantomData<SlimmerMetadata>,
-)
+impl<T, SlimmerMetadata, Input> PointerMetadataDoesNotFitError<T, SlimmerMetadata, Input>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata>;
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    pub(crate) fn new(metadata: <T as Pointee>::Metadata, input: Input) -> Self {
+        Self {
+            metadata,
+            input,
+            marker: PhantomData,
+            slimmer_marker: PhantomData,
+        }
+    }
 
-impl<T, SlimmerMetadata> core::fmt::Debug for PointerMetadataDoesNotFitError<T, SlimmerMetadata>
+    /// The metadata which did not fit. For slices and strings, this is their length.
+    pub fn metadata(&self) -> <T as Pointee>::Metadata {
+        self.metadata
+    }
+
+    /// Returns a reference to the input that was handed back.
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    /// Takes back the input which could not be made slimmer.
+    pub fn into_input(self) -> Input {
+        self.input
+    }
+
+    /// Drops the input, leaving only the description of what went wrong.
+    ///
+    /// Useful to convert between the errors of different constructors.
+    pub fn without_input(self) -> PointerMetadataDoesNotFitError<T, SlimmerMetadata> {
+        PointerMetadataDoesNotFitError::new(self.metadata, ())
+    }
+}
+
+impl<T, SlimmerMetadata, Input> core::fmt::Debug
+    for PointerMetadataDoesNotFitError<T, SlimmerMetadata, Input>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.debug_struct("PointerMetadataDoesNotFitError")
@@ -265,16 +413,17 @@ where
                 "slimmer_metadata_type",
                 &core::any::type_name::<SlimmerMetadata>(),
             )
+            .field("input_type", &core::any::type_name::<Input>())
             .finish()
     }
 }
 
-impl<T, SlimmerMetadata> core::fmt::Display for PointerMetadataDoesNotFitError<T, SlimmerMetadata>
+impl<T, SlimmerMetadata, Input> core::fmt::Display
+    for PointerMetadataDoesNotFitError<T, SlimmerMetadata, Input>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata>,
-    // <T as Pointee>::Metadata: core::fmt::Debug,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         write!(
@@ -288,11 +437,118 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl<T, SlimmerMetadata, Input> std::error::Error
+    for PointerMetadataDoesNotFitError<T, SlimmerMetadata, Input>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+#[cfg(feature = "alloc")]
+/// The error returned by the `try_new_fallible` family of constructors.
+///
+/// Unlike `try_new`, these do not abort the process when the allocator fails,
+/// but return [`AllocError::AllocationFailed`] instead.
+pub enum AllocError<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// The value's metadata is too large to fit in `SlimmerMetadata`.
+    /// This is checked before anything is allocated.
+    MetadataDoesNotFit(PointerMetadataDoesNotFitError<T, SlimmerMetadata>),
+    /// The allocator could not provide memory for the given layout.
+    AllocationFailed(core::alloc::Layout),
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> From<PointerMetadataDoesNotFitError<T, SlimmerMetadata>>
+    for AllocError<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn from(error: PointerMetadataDoesNotFitError<T, SlimmerMetadata>) -> Self {
+        AllocError::MetadataDoesNotFit(error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> AllocError<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Turns the error back into the error of the non-fallible family,
+    /// calling [`handle_alloc_error`](alloc::alloc::handle_alloc_error) if the allocation failed.
+    pub(crate) fn or_handle_alloc_error(self) -> PointerMetadataDoesNotFitError<T, SlimmerMetadata> {
+        match self {
+            AllocError::MetadataDoesNotFit(error) => error,
+            AllocError::AllocationFailed(layout) => alloc::alloc::handle_alloc_error(layout),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> core::fmt::Debug for AllocError<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            AllocError::MetadataDoesNotFit(error) => {
+                f.debug_tuple("MetadataDoesNotFit").field(error).finish()
+            }
+            AllocError::AllocationFailed(layout) => {
+                f.debug_tuple("AllocationFailed").field(layout).finish()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> core::fmt::Display for AllocError<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            AllocError::MetadataDoesNotFit(error) => core::fmt::Display::fmt(error, f),
+            AllocError::AllocationFailed(layout) => write!(
+                f,
+                "Memory allocation of {} bytes (aligned to {}) failed",
+                layout.size(),
+                layout.align()
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, SlimmerMetadata> std::error::Error for AllocError<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+#[cfg(feature = "alloc")]
 impl<T, SlimmerMetadata> SlimmerBox<T, SlimmerMetadata>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 {
     /// Creates a new SlimmerBox from the given value (which may be a slice, string or other dynamically sized type).
     ///
@@ -324,33 +580,60 @@ where
     where
         T: CloneUnsized,
     {
-        let meta = ptr_meta::metadata(value);
-        let target_ptr = if core::mem::size_of_val(value) > 0 {
-            // Normally-sized type (or DST with non-empty size):
-            let layout = core::alloc::Layout::for_value(value);
-            let alloc_ptr = unsafe { alloc::alloc::alloc(layout) } as *mut ();
-            let target_ptr: *mut T = ptr_meta::from_raw_parts_mut(alloc_ptr, meta);
-            // SAFETY: We obtain a reference to newly allocated space
-            // This is not yet a valid T, but we only use it to immediately write into
-            unsafe { &mut *target_ptr }.unsized_clone_from(value);
-            target_ptr
-        } else {
-            // ZST, (or DST with zero size like an empty slice)
-            // no allocation needed nor desired
-            core::ptr::addr_of!(*value) as *mut T
-        };
+        Self::try_new_in(value, Global)
+    }
+
+    /// Variant of `try_new` which will also return an error if allocating memory fails,
+    /// rather than calling [`handle_alloc_error`](alloc::alloc::handle_alloc_error).
+    pub fn try_new_fallible(value: &T) -> Result<Self, AllocError<T, SlimmerMetadata>>
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new_fallible_in(value, Global)
+    }
+
+    /// Variant of `new` for values whose contents are `Copy`, like slices of integers.
+    ///
+    /// Rather than cloning the elements one by one, the whole value is copied using a single memcpy.
+    ///
+    /// ```rust
+    /// use slimmer_box::SlimmerBox;
+    /// let boxed: SlimmerBox<[u16], u8> = SlimmerBox::new_copied(&[1, 2, 3]);
+    /// assert_eq!(&*boxed, &[1, 2, 3]);
+    /// ```
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata.
+    pub fn new_copied(value: &T) -> Self
+    where
+        T: CopyUnsized,
+    {
+        Self::try_new_copied(value).unwrap()
+    }
 
-        // SAFETY: We pass a newly allocated, filled, ptr
-        unsafe { Self::try_from_raw(target_ptr) }
+    /// Variant of `new_copied` which will return an error if the slice is too long instead of panicing.
+    pub fn try_new_copied(
+        value: &T,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>>
+    where
+        T: CopyUnsized,
+    {
+        Self::try_new_with_in(value, Global, T::copy_to_uninit)
+            .map_err(AllocError::or_handle_alloc_error)
     }
 
     /// Variant of `from_box` which will return an error if the value's metadata is too large instead of panicing.
+    ///
+    /// On error, the box is handed back inside the error.
     pub fn try_from_box(
         boxed: Box<T>,
-    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>> {
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata, Box<T>>> {
+        let meta = ptr_meta::metadata(&*boxed);
+        if SlimmerMetadata::try_compress(meta).is_none() {
+            return Err(PointerMetadataDoesNotFitError::new(meta, boxed));
+        }
         let fat_ptr = Box::into_raw(boxed);
-        // SAFETY: Box ensures fat_ptr is non-null
-        unsafe { Self::try_from_raw(fat_ptr) }
+        // SAFETY: Box ensures fat_ptr is non-null, and the metadata was checked above
+        Ok(unsafe { Self::try_from_raw(fat_ptr).unwrap_unchecked() })
     }
 
     /// Builds a new SlimmerBox from a raw mutable pointer
@@ -366,6 +649,7 @@ where
     }
 
     /// Variant of `from_raw` which will return an error if the value's metadata is too large instead of panicing.
+    /// On error, ownership of `target_ptr` stays with the caller.
     ///
     /// # Safety
     /// Caller must ensure *T is valid, and non-null
@@ -375,15 +659,15 @@ where
         target_ptr: *mut T,
     ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>> {
         let (thin_ptr, meta) = ptr_meta::PtrExt::to_raw_parts(target_ptr);
-        let slim_meta = meta
-            .try_into()
-            .map_err(|_| PointerMetadataDoesNotFitError(PhantomData, PhantomData))?;
+        let slim_meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
 
         // SAFETY: Box ensures its ptr is never null.
-        let ptr = unsafe { core::ptr::NonNull::new_unchecked(thin_ptr as *mut ()) };
+        let ptr = unsafe { core::ptr::NonNull::new_unchecked(thin_ptr) };
         Ok(Self {
             ptr,
             meta: slim_meta,
+            alloc: ManuallyDrop::new(Global),
             marker: PhantomData,
         })
     }
@@ -416,18 +700,6 @@ where
         unsafe { Box::from_raw(ptr) }
     }
 
-    /// Obtains a raw read-only (non-owned) pointer view of the contents of this SlimmerBox.
-    ///
-    /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
-    ///
-    /// This function is mainly useful if you need to implement something that exists for Box
-    /// but not (yet) for SlimmerBox. Feel free to open an issue or contribute a PR!
-    ///
-    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
-    pub fn to_ptr(this: &Self) -> *const T {
-        ptr_meta::from_raw_parts(this.ptr.as_ptr(), SlimmerBox::metadata(this))
-    }
-
     /// Turns the SlimmerBox into a raw pointer
     ///
     /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
@@ -442,184 +714,806 @@ where
         core::mem::forget(this);
         ptr
     }
+}
 
-    /// Retrieve access to the stored slimmer metadata value.
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> SlimmerBox<T, SlimmerMetadata>
+where
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Creates a new SlimmerBox by moving the given (sized) value onto the heap.
     ///
-    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
-    pub fn slim_metadata(this: &Self) -> SlimmerMetadata {
-        this.meta
+    /// Unlike `new`, this does not require `T` to be cloneable.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata.
+    /// (For sized types the metadata is `()`, so this only happens for unusual choices of SlimmerMetadata.)
+    pub fn from_value(value: T) -> Self {
+        Self::from_box(Box::new(value))
     }
 
-    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    /// Moves the value out of the SlimmerBox, deallocating it.
     ///
     /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
-    pub fn metadata(this: &Self) -> <T as Pointee>::Metadata {
-        let aligned_len = SlimmerBox::slim_metadata(this);
-        // SAFETY: Guaranteed to not fail by the unsafe SlimmerPointee trait
-        unsafe { aligned_len.try_into().unwrap_unchecked() }
+    pub fn into_inner(this: Self) -> T {
+        *SlimmerBox::into_box(this)
     }
-}
 
-unsafe impl<T: Send, SlimmerMetadata: Send> Send for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{}
+    /// Creates a new pinned SlimmerBox by moving the given (sized) value onto the heap.
+    ///
+    /// If `T` does not implement `Unpin`, the value will never be moved again.
+    pub fn pin(value: T) -> core::pin::Pin<Self> {
+        // SAFETY: The value lives on the heap and is never moved out of a pinned SlimmerBox,
+        // as that would require a `&mut SlimmerBox` or owned SlimmerBox, which Pin does not hand out.
+        unsafe { core::pin::Pin::new_unchecked(Self::from_value(value)) }
+    }
+}
 
-unsafe impl<T: Sync, SlimmerMetadata: Sync> Sync for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{}
+#[cfg(feature = "alloc")]
+/// Compile-time check that arrays of length `N` fit in `SlimmerMetadata`.
+///
+/// Comparing against `MAX` is enough, as the safety contract of [`SlimMetadata`]
+/// guarantees that every length up to and including `MAX` can be compressed.
+struct ArrayLengthFits<SlimmerMetadata, const N: usize>(PhantomData<SlimmerMetadata>);
 
-impl<T, SlimmerMetadata> Drop for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<SlimmerMetadata, const N: usize> ArrayLengthFits<SlimmerMetadata, N>
 where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<usize>,
 {
-    fn drop(&mut self) {
-        // NOTE: The garbage value will not be dropped which is exactly what we want
-        let me = core::mem::replace(
-            self,
-            SlimmerBox {
-                ptr: NonNull::dangling(),
-                meta: self.meta,
-                marker: PhantomData,
-            },
-        );
-        let _drop_this_box = SlimmerBox::into_box(me);
-    }
+    const ASSERT: () = assert!(
+        N <= SlimmerMetadata::MAX,
+        "The array length does not fit in the chosen SlimmerMetadata"
+    );
 }
 
-impl<T, SlimmerMetadata> Deref for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> SlimmerBox<[T], SlimmerMetadata>
 where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    [T]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
 {
-    type Target = T;
-    fn deref(&self) -> &Self::Target {
-        let ptr = ptr_meta::from_raw_parts(self.ptr.as_ptr(), SlimmerBox::metadata(self));
-        // SAFETY: Correct by construction
-        unsafe { &*ptr }
+    /// Creates a new SlimmerBox by moving the elements of the given array onto the heap.
+    ///
+    /// Whether `N` fits in SlimmerMetadata is checked at compile time,
+    /// so unlike `from_box` this never needs to check (or panic) at runtime:
+    ///
+    /// ```rust
+    /// use slimmer_box::SlimmerBox;
+    /// let boxed: SlimmerBox<[u8], u8> = SlimmerBox::from_array([1, 2, 3]);
+    /// assert_eq!(&*boxed, &[1, 2, 3]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use slimmer_box::SlimmerBox;
+    /// let boxed: SlimmerBox<[u8], u8> = SlimmerBox::from_array([0; 256]);
+    /// ```
+    pub fn from_array<const N: usize>(array: [T; N]) -> Self {
+        let () = ArrayLengthFits::<SlimmerMetadata, N>::ASSERT;
+        let boxed: Box<[T]> = Box::new(array);
+        // SAFETY: N <= SlimmerMetadata::MAX was checked at compile time, so N can be compressed
+        unsafe { Self::from_box_unchecked(boxed) }
     }
-}
 
-impl<T, SlimmerMetadata> DerefMut for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        let ptr = ptr_meta::from_raw_parts_mut(self.ptr.as_ptr(), SlimmerBox::metadata(self));
-        // SAFETY: Correct by construction
-        unsafe { &mut *ptr }
+    /// Creates a new SlimmerBox by cloning the elements of the given array.
+    ///
+    /// Just like `from_array`, whether `N` fits in SlimmerMetadata is checked at compile time.
+    pub fn new_const_checked<const N: usize>(array: &[T; N]) -> Self
+    where
+        T: Clone,
+    {
+        let () = ArrayLengthFits::<SlimmerMetadata, N>::ASSERT;
+        // SAFETY: N <= SlimmerMetadata::MAX was checked at compile time, so N can be compressed
+        unsafe { Self::new_unchecked(array.as_slice()) }
     }
-}
 
-impl<T, SlimmerMetadata> core::borrow::Borrow<T> for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-    fn borrow(&self) -> &T {
-        self
+    /// Creates a new SlimmerBox of `len` elements, calling `f` with the index of each element to create it.
+    ///
+    /// The elements are written directly into a single allocation of the final size:
+    ///
+    /// ```rust
+    /// use slimmer_box::SlimmerBox;
+    /// let squares: SlimmerBox<[usize], u8> = SlimmerBox::from_fn(5, |index| index * index);
+    /// assert_eq!(&*squares, &[0, 1, 4, 9, 16]);
+    /// ```
+    ///
+    /// Panics if `len` is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    /// Also panics if the total size in bytes overflows `isize::MAX`, just like `Vec::with_capacity`.
+    ///
+    /// If `f` panics, the elements created so far are dropped and the allocation is freed.
+    pub fn from_fn<F>(len: usize, f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+        [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    {
+        Self::try_from_fn(len, f).unwrap()
     }
-}
 
-impl<T, SlimmerMetadata> core::borrow::BorrowMut<T> for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-    fn borrow_mut(&mut self) -> &mut T {
-        self
+    /// Variant of `from_fn` which will return an error if `len` is too large instead of panicing.
+    ///
+    /// This still panics if the total size in bytes overflows `isize::MAX`, just like `from_fn`.
+    pub fn try_from_fn<F>(
+        len: usize,
+        mut f: F,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[T], SlimmerMetadata>>
+    where
+        F: FnMut(usize) -> T,
+        [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    {
+        let meta = ptr_meta::metadata(core::ptr::slice_from_raw_parts(
+            core::ptr::null::<T>(),
+            len,
+        ));
+        let mut uninit: SlimmerBox<[MaybeUninit<T>], SlimmerMetadata> =
+            SlimmerBox::try_new_uninit_slice(len)
+                .map_err(|_| PointerMetadataDoesNotFitError::new(meta, ()))?;
+
+        // NOTE: Dropped before `uninit` on unwind, so the elements are dropped before the allocation is freed.
+        let mut guard = PartialSliceGuard {
+            dest: uninit.as_mut_ptr() as *mut T,
+            initialized: 0,
+        };
+        while guard.initialized < len {
+            // SAFETY: There is room for `len` elements
+            unsafe {
+                guard
+                    .dest
+                    .add(guard.initialized)
+                    .write(f(guard.initialized))
+            };
+            guard.initialized += 1;
+        }
+        core::mem::forget(guard);
+
+        // SAFETY: All `len` elements were initialized above
+        Ok(unsafe { SlimmerBox::assume_init(uninit) })
     }
-}
 
-impl<T, SlimmerMetadata> AsRef<T> for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-    fn as_ref(&self) -> &T {
-        self
+    /// Creates a new SlimmerBox containing `len` clones of `value`.
+    ///
+    /// Panics if `len` is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    pub fn repeat(value: T, len: usize) -> Self
+    where
+        T: Clone,
+        [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    {
+        Self::from_fn(len, |_| value.clone())
     }
-}
 
-impl<T, SlimmerMetadata> AsMut<T> for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-    fn as_mut(&mut self) -> &mut T {
-        self
+    /// Creates a new SlimmerBox containing the elements of the given iterator,
+    /// using its exact length to allocate once at the final size.
+    ///
+    /// Panics if the length is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    ///
+    /// Also panics if the iterator yields fewer elements than it promised; superfluous elements are ignored.
+    pub fn from_exact_size_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+        [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    {
+        Self::try_from_exact_size_iter(iter).unwrap()
     }
-}
 
-impl<T, SlimmerMetadata> Unpin for SlimmerBox<T, SlimmerMetadata>
-where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-}
+    /// Variant of `from_exact_size_iter` which will return an error if the length is too large instead of panicing.
+    pub fn try_from_exact_size_iter<I>(
+        iter: I,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[T], SlimmerMetadata>>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+        [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    {
+        let mut iter = iter.into_iter();
+        Self::try_from_fn(iter.len(), |_| {
+            iter.next()
+                .expect("ExactSizeIterator yielded fewer elements than its len()")
+        })
+    }
 
-impl<T, SlimmerMetadata> Clone for SlimmerBox<T, SlimmerMetadata>
-where
-    T: CloneUnsized,
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
-{
-    fn clone(&self) -> Self {
-        let value = self.deref();
-        // SAFETY: The original SlimmerBox already checked this invariant on construction
-        unsafe { SlimmerBox::new_unchecked(value) }
+    /// Turns the SlimmerBox into a Vec, without copying or reallocating.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_vec(this: Self) -> Vec<T> {
+        SlimmerBox::into_box(this).into_vec()
     }
 }
 
-impl<T, SlimmerMetadata> core::fmt::Debug for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<SlimmerMetadata> SlimmerBox<str, SlimmerMetadata>
 where
-    T: core::fmt::Debug,
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
 {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        core::fmt::Debug::fmt(&**self, f)
+    /// Turns the SlimmerBox into a String, without copying or reallocating.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_string(this: Self) -> String {
+        SlimmerBox::into_box(this).into_string()
     }
 }
 
-impl<T: PartialEq, SlimmerMetadata> PartialEq for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> SlimmerBox<[MaybeUninit<T>], SlimmerMetadata>
 where
-    T: ?Sized,
-    T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[MaybeUninit<T>] as Pointee>::Metadata>,
 {
-    #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        PartialEq::eq(&**self, &**other)
+    /// Creates a new SlimmerBox containing `len` uninitialized elements.
+    ///
+    /// This allows filling (large) buffers directly in their final allocation,
+    /// after which [`assume_init`](SlimmerBox::assume_init) turns it into a `SlimmerBox<[T]>`:
+    ///
+    /// ```rust
+    /// use slimmer_box::SlimmerBox;
+    /// let mut buffer: SlimmerBox<[_], u16> = SlimmerBox::new_uninit_slice(3);
+    /// for (index, elem) in buffer.iter_mut().enumerate() {
+    ///     elem.write(index as u64);
+    /// }
+    /// let buffer = unsafe { SlimmerBox::assume_init(buffer) };
+    /// assert_eq!(&*buffer, &[0, 1, 2]);
+    /// ```
+    ///
+    /// Panics if `len` is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    /// Also panics if the total size in bytes overflows `isize::MAX`, just like `Vec::with_capacity`.
+    pub fn new_uninit_slice(len: usize) -> Self {
+        Self::new_uninit_slice_in(len, Global)
     }
-    #[inline]
-    fn ne(&self, other: &Self) -> bool {
-        PartialEq::ne(&**self, &**other)
+
+    /// Variant of `new_uninit_slice` which will return an error if `len` is too large instead of panicing.
+    ///
+    /// This still panics if the total size in bytes overflows `isize::MAX`, just like `new_uninit_slice`.
+    pub fn try_new_uninit_slice(
+        len: usize,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[MaybeUninit<T>], SlimmerMetadata>> {
+        Self::try_new_uninit_slice_in(len, Global)
+    }
+
+    /// Creates a new SlimmerBox containing `len` elements whose memory is filled with zero bytes.
+    ///
+    /// Rather than writing the zeroes by hand, this asks the allocator for zeroed memory (i.e. uses `calloc`),
+    /// which is often a lot faster for large buffers.
+    ///
+    /// Panics if `len` is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    /// Also panics if the total size in bytes overflows `isize::MAX`, just like `Vec::with_capacity`.
+    pub fn new_zeroed_slice(len: usize) -> Self {
+        Self::new_zeroed_slice_in(len, Global)
+    }
+
+    /// Variant of `new_zeroed_slice` which will return an error if `len` is too large instead of panicing.
+    ///
+    /// This still panics if the total size in bytes overflows `isize::MAX`, just like `new_zeroed_slice`.
+    pub fn try_new_zeroed_slice(
+        len: usize,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[MaybeUninit<T>], SlimmerMetadata>> {
+        Self::try_new_zeroed_slice_in(len, Global)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> SlimmerBox<[MaybeUninit<T>], SlimmerMetadata, A>
+where
+    [MaybeUninit<T>]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[MaybeUninit<T>] as Pointee>::Metadata>,
+    A: Allocator,
+{
+    /// Variant of `new_uninit_slice` which allocates using the given allocator.
+    ///
+    /// Panics if `len` is too large to fit in SlimmerMetadata.
+    pub fn new_uninit_slice_in(len: usize, alloc: A) -> Self {
+        Self::try_new_uninit_slice_in(len, alloc).unwrap()
+    }
+
+    /// Variant of `new_uninit_slice_in` which will return an error if `len` is too large instead of panicing.
+    ///
+    /// This still panics if the total size in bytes overflows `isize::MAX`, just like `new_uninit_slice_in`.
+    pub fn try_new_uninit_slice_in(
+        len: usize,
+        alloc: A,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[MaybeUninit<T>], SlimmerMetadata>> {
+        Self::try_new_slice_in(len, alloc, false).map_err(AllocError::or_handle_alloc_error)
+    }
+
+    /// Variant of `new_zeroed_slice` which allocates using the given allocator.
+    ///
+    /// Panics if `len` is too large to fit in SlimmerMetadata.
+    pub fn new_zeroed_slice_in(len: usize, alloc: A) -> Self {
+        Self::try_new_zeroed_slice_in(len, alloc).unwrap()
+    }
+
+    /// Variant of `new_zeroed_slice_in` which will return an error if `len` is too large instead of panicing.
+    ///
+    /// This still panics if the total size in bytes overflows `isize::MAX`, just like `new_zeroed_slice_in`.
+    pub fn try_new_zeroed_slice_in(
+        len: usize,
+        alloc: A,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[MaybeUninit<T>], SlimmerMetadata>> {
+        Self::try_new_slice_in(len, alloc, true).map_err(AllocError::or_handle_alloc_error)
+    }
+
+    fn try_new_slice_in(
+        len: usize,
+        alloc: A,
+        zeroed: bool,
+    ) -> Result<Self, AllocError<[MaybeUninit<T>], SlimmerMetadata>> {
+        let meta = ptr_meta::metadata(core::ptr::slice_from_raw_parts(
+            core::ptr::null::<MaybeUninit<T>>(),
+            len,
+        ));
+        let slim_meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
+        let layout = core::alloc::Layout::array::<T>(len).expect("allocation size overflow");
+        let target_ptr = if layout.size() > 0 {
+            let alloc_ptr = if zeroed {
+                alloc.allocate_zeroed(layout)
+            } else {
+                alloc.allocate(layout)
+            };
+            alloc_ptr
+                .map_err(|_| AllocError::AllocationFailed(layout))?
+                .cast::<u8>()
+                .as_ptr()
+        } else {
+            // Nothing to allocate, so we use a dangling but well-aligned pointer.
+            layout.align() as *mut u8
+        };
+
+        Ok(Self {
+            // SAFETY: Either a fresh allocation or dangling, never null
+            ptr: unsafe { NonNull::new_unchecked(target_ptr as *mut ()) },
+            meta: slim_meta,
+            alloc: ManuallyDrop::new(alloc),
+            marker: PhantomData,
+        })
+    }
+
+    /// Converts a `SlimmerBox<[MaybeUninit<T>]>` into a `SlimmerBox<[T]>`, re-using the allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    ///
+    /// # Safety
+    /// Just like with [`MaybeUninit::assume_init`],
+    /// the caller must guarantee that all elements have been initialized.
+    pub unsafe fn assume_init(this: Self) -> SlimmerBox<[T], SlimmerMetadata, A>
+    where
+        [T]: SlimmerPointee<SlimmerMetadata>,
+        SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+    {
+        let meta = this.meta;
+        let (ptr, alloc) = SlimmerBox::into_raw_with_allocator(this);
+        SlimmerBox {
+            ptr: NonNull::new_unchecked(ptr as *mut ()),
+            meta,
+            alloc: ManuallyDrop::new(alloc),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    /// Variant of `new` which allocates the clone using the given allocator.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata.
+    pub fn new_in(value: &T, alloc: A) -> Self
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new_in(value, alloc).unwrap()
+    }
+
+    /// Variant of `new_in` that skips its size check.
+    ///
+    /// # Safety
+    /// The caller must ensure that the value's metadata fits in SlimmerMetadata.
+    pub unsafe fn new_unchecked_in(value: &T, alloc: A) -> Self
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new_in(value, alloc).unwrap_unchecked()
+    }
+
+    /// Variant of `new_in` which will return an error if the slice is too long instead of panicing.
+    pub fn try_new_in(
+        value: &T,
+        alloc: A,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>>
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new_fallible_in(value, alloc).map_err(AllocError::or_handle_alloc_error)
+    }
+
+    /// Variant of `try_new_in` which will also return an error if allocating memory fails,
+    /// rather than calling [`handle_alloc_error`](alloc::alloc::handle_alloc_error).
+    pub fn try_new_fallible_in(
+        value: &T,
+        alloc: A,
+    ) -> Result<Self, AllocError<T, SlimmerMetadata>>
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new_with_in(value, alloc, T::clone_to_uninit)
+    }
+
+    /// Allocates room for a clone of `value` and uses `write` (`clone_to_uninit` or `copy_to_uninit`) to fill it.
+    fn try_new_with_in(
+        value: &T,
+        alloc: A,
+        write: unsafe fn(&T, *mut u8),
+    ) -> Result<Self, AllocError<T, SlimmerMetadata>> {
+        let meta = ptr_meta::metadata(value);
+        let slim_meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
+        let layout = core::alloc::Layout::for_value(value);
+        let target_ptr = if layout.size() > 0 {
+            // Normally-sized type (or DST with non-empty size):
+            let alloc_ptr = alloc
+                .allocate(layout)
+                .map_err(|_| AllocError::AllocationFailed(layout))?
+                .cast::<u8>();
+            let guard = OnUnwind(|| unsafe { alloc.deallocate(alloc_ptr, layout) });
+            // SAFETY: Newly allocated space, large enough and properly aligned
+            unsafe { write(value, alloc_ptr.as_ptr()) };
+            core::mem::forget(guard);
+            alloc_ptr.as_ptr()
+        } else {
+            // ZST, (or DST with zero size like an empty slice)
+            // no allocation needed nor desired, so we use a dangling but well-aligned pointer.
+            // (Cloning is still needed, as the elements' Clone impls might have side effects.)
+            let dangling_ptr = layout.align() as *mut u8;
+            // SAFETY: Writes of zero bytes only require a non-null, aligned pointer
+            unsafe { write(value, dangling_ptr) };
+            dangling_ptr
+        };
+
+        Ok(Self {
+            // SAFETY: Either a fresh allocation or dangling, never null
+            ptr: unsafe { NonNull::new_unchecked(target_ptr as *mut ()) },
+            meta: slim_meta,
+            alloc: ManuallyDrop::new(alloc),
+            marker: PhantomData,
+        })
+    }
+
+    /// Variant of `from_raw` for a pointer that was allocated using the given allocator.
+    ///
+    /// Panics if the type's metadata is too large.
+    ///
+    /// # Safety
+    /// Caller must ensure *T is valid, and non-null,
+    /// and that it was allocated by `alloc` (unless T's size is zero).
+    ///
+    /// Furthermore, similar caveats apply as with Box::from_raw_in.
+    pub unsafe fn from_raw_in(target_ptr: *mut T, alloc: A) -> Self {
+        Self::try_from_raw_in(target_ptr, alloc).unwrap()
+    }
+
+    /// Variant of `from_raw_in` which will return an error if the value's metadata is too large instead of panicing.
+    /// On error, ownership of `target_ptr` stays with the caller.
+    ///
+    /// # Safety
+    /// Caller must ensure *T is valid, and non-null,
+    /// and that it was allocated by `alloc` (unless T's size is zero).
+    ///
+    /// Furthermore, similar caveats apply as with Box::from_raw_in.
+    pub unsafe fn try_from_raw_in(
+        target_ptr: *mut T,
+        alloc: A,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>> {
+        let (thin_ptr, meta) = ptr_meta::PtrExt::to_raw_parts(target_ptr);
+        let slim_meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
+
+        Ok(Self {
+            ptr: NonNull::new_unchecked(thin_ptr),
+            meta: slim_meta,
+            alloc: ManuallyDrop::new(alloc),
+            marker: PhantomData,
+        })
+    }
+
+    /// Turns an [`allocator_api2::boxed::Box`] using a custom allocator into a SlimmerBox using the same allocator.
+    ///
+    /// This is a fast constant-time operation that needs no allocation, as it consumes the box.
+    ///
+    /// Panics if the pointer's metadata is too large to made slimmer.
+    pub fn from_box_in(boxed: allocator_api2::boxed::Box<T, A>) -> Self {
+        Self::try_from_box_in(boxed).unwrap()
+    }
+
+    /// Variant of `from_box_in` which will return an error if the value's metadata is too large instead of panicing.
+    ///
+    /// On error, the box is handed back inside the error.
+    pub fn try_from_box_in(
+        boxed: allocator_api2::boxed::Box<T, A>,
+    ) -> Result<
+        Self,
+        PointerMetadataDoesNotFitError<T, SlimmerMetadata, allocator_api2::boxed::Box<T, A>>,
+    > {
+        let meta = ptr_meta::metadata(&*boxed);
+        if SlimmerMetadata::try_compress(meta).is_none() {
+            return Err(PointerMetadataDoesNotFitError::new(meta, boxed));
+        }
+        let (fat_ptr, alloc) = allocator_api2::boxed::Box::into_raw_with_allocator(boxed);
+        // SAFETY: Box ensures fat_ptr is non-null and allocated by `alloc`, and the metadata was checked above
+        Ok(unsafe { Self::try_from_raw_in(fat_ptr, alloc).unwrap_unchecked() })
+    }
+
+    /// Turns a SlimmerBox into an [`allocator_api2::boxed::Box`] using the same allocator.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_box_in(this: Self) -> allocator_api2::boxed::Box<T, A> {
+        let (ptr, alloc) = Self::into_raw_with_allocator(this);
+        // SAFETY: We reconstruct using the inverse operations from construction
+        unsafe { allocator_api2::boxed::Box::from_raw_in(ptr, alloc) }
+    }
+
+    /// Turns the SlimmerBox into a raw pointer, together with the allocator that was used to allocate it.
+    ///
+    /// Similar caveats apply as to Box::into_raw_with_allocator.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_raw_with_allocator(this: Self) -> (*mut T, A) {
+        let ptr = ptr_meta::from_raw_parts_mut(this.ptr.as_ptr(), SlimmerBox::metadata(&this));
+        // SAFETY: `this` is forgotten right after, so the allocator is not dropped twice
+        let alloc = unsafe { core::ptr::read_unaligned(core::ptr::addr_of!(this.alloc)) };
+        core::mem::forget(this);
+        (ptr, ManuallyDrop::into_inner(alloc))
+    }
+
+    /// Obtains a raw read-only (non-owned) pointer view of the contents of this SlimmerBox.
+    ///
+    /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
+    ///
+    /// This function is mainly useful if you need to implement something that exists for Box
+    /// but not (yet) for SlimmerBox. Feel free to open an issue or contribute a PR!
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        ptr_meta::from_raw_parts(this.ptr.as_ptr(), SlimmerBox::metadata(this))
+    }
+
+    /// Borrows the contents of this SlimmerBox as a [`SlimmerRef`], which is as slim as the SlimmerBox itself.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn as_slim_ref(this: &Self) -> SlimmerRef<'_, T, SlimmerMetadata> {
+        // SAFETY: The metadata was already checked when constructing the SlimmerBox
+        unsafe { SlimmerRef::from_parts(this.ptr, this.meta) }
+    }
+
+    /// Mutably borrows the contents of this SlimmerBox as a [`SlimmerMut`], which is as slim as the SlimmerBox itself.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn as_slim_mut(this: &mut Self) -> SlimmerMut<'_, T, SlimmerMetadata> {
+        // SAFETY: The metadata was already checked when constructing the SlimmerBox,
+        // and we are borrowed mutably for the lifetime of the result
+        unsafe { SlimmerMut::from_parts(this.ptr, this.meta) }
+    }
+
+    /// Retrieve access to the stored slimmer metadata value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn slim_metadata(this: &Self) -> SlimmerMetadata {
+        this.meta
+    }
+
+    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> <T as Pointee>::Metadata {
+        let aligned_len = SlimmerBox::slim_metadata(this);
+        aligned_len.expand()
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: Send, SlimmerMetadata: Send, A: Send> Send for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: Sync, SlimmerMetadata: Sync, A: Sync> Sync for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> Drop for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        let ptr: *mut T = ptr_meta::from_raw_parts_mut(self.ptr.as_ptr(), SlimmerBox::metadata(self));
+        // SAFETY: Correct by construction.
+        // The allocator is moved out exactly once, as this SlimmerBox is never used again.
+        unsafe {
+            let layout = core::alloc::Layout::for_value(&*ptr);
+            let alloc = ManuallyDrop::into_inner(core::ptr::read_unaligned(core::ptr::addr_of!(self.alloc)));
+            let alloc_ptr: NonNull<u8> = self.ptr.cast();
+            // Deallocates both after dropping the value and when dropping it panics.
+            // ZSTs (and DSTs with zero size like an empty slice) were never allocated
+            let _dealloc = OnUnwind(|| {
+                if layout.size() != 0 {
+                    alloc.deallocate(alloc_ptr, layout);
+                }
+            });
+            core::ptr::drop_in_place(ptr);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> Deref for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        let ptr = ptr_meta::from_raw_parts(self.ptr.as_ptr(), SlimmerBox::metadata(self));
+        // SAFETY: Correct by construction
+        unsafe { &*ptr }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> DerefMut for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let ptr = ptr_meta::from_raw_parts_mut(self.ptr.as_ptr(), SlimmerBox::metadata(self));
+        // SAFETY: Correct by construction
+        unsafe { &mut *ptr }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> core::borrow::Borrow<T> for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> core::borrow::BorrowMut<T> for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> AsRef<T> for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> AsMut<T> for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> Unpin for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> Clone for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: CloneUnsized,
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator + Clone,
+{
+    fn clone(&self) -> Self {
+        let value = self.deref();
+        // SAFETY: The allocator is only read to clone it; the copy is never dropped
+        let alloc = unsafe { core::ptr::read_unaligned(core::ptr::addr_of!(self.alloc)) };
+        let alloc = A::clone(&alloc);
+        // SAFETY: The original SlimmerBox already checked this invariant on construction
+        unsafe { SlimmerBox::new_unchecked_in(value, alloc) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata, A> core::fmt::Debug for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
     }
 }
 
-impl<T: PartialOrd, SlimmerMetadata> PartialOrd for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<T: PartialEq, SlimmerMetadata, A> PartialEq for SlimmerBox<T, SlimmerMetadata, A>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+    #[inline]
+    #[allow(clippy::partialeq_ne_impl)]
+    fn ne(&self, other: &Self) -> bool {
+        PartialEq::ne(&**self, &**other)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PartialOrd, SlimmerMetadata, A> PartialOrd for SlimmerBox<T, SlimmerMetadata, A>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
 {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
@@ -643,37 +1537,237 @@ where
     }
 }
 
-impl<T: Ord, SlimmerMetadata> Ord for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<T: Ord, SlimmerMetadata, A> Ord for SlimmerBox<T, SlimmerMetadata, A>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
 {
     #[inline]
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         Ord::cmp(&**self, &**other)
     }
 }
-impl<T: Eq, SlimmerMetadata> Eq for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<T: Eq, SlimmerMetadata, A> Eq for SlimmerBox<T, SlimmerMetadata, A>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
 {
 }
 
-impl<T: core::hash::Hash, SlimmerMetadata> core::hash::Hash for SlimmerBox<T, SlimmerMetadata>
+#[cfg(feature = "alloc")]
+impl<T: core::hash::Hash, SlimmerMetadata, A> core::hash::Hash for SlimmerBox<T, SlimmerMetadata, A>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+    A: Allocator,
 {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         (**self).hash(state);
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "alloc")]
+// NOTE: `TryFrom<Box<T>>` cannot be implemented generically:
+// as Box is a fundamental type, other crates may implement `Into<SlimmerBox<Local>> for Box<Local>`,
+// which would overlap with core's blanket `impl<T, U: Into<T>> TryFrom<U> for T`.
+// Hence it is implemented for the unsized pointees supported by this crate instead;
+// sized values can use `try_from_box`.
+macro_rules! impl_try_from_box {
+    ($({$($generics:tt)*} $pointee:ty),* $(,)?) => {
+        $(
+            impl<$($generics)* SlimmerMetadata> TryFrom<Box<$pointee>>
+                for SlimmerBox<$pointee, SlimmerMetadata>
+            where
+                $pointee: SlimmerPointee<SlimmerMetadata>,
+                SlimmerMetadata: SlimMetadata<<$pointee as Pointee>::Metadata>,
+            {
+                type Error =
+                    PointerMetadataDoesNotFitError<$pointee, SlimmerMetadata, Box<$pointee>>;
+
+                fn try_from(boxed: Box<$pointee>) -> Result<Self, Self::Error> {
+                    SlimmerBox::try_from_box(boxed)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl_try_from_box!({T,} [T], {} str);
+
+#[cfg(feature = "std")]
+impl_try_from_box!({} ::std::ffi::CStr, {} ::std::ffi::OsStr);
+
+#[cfg(feature = "alloc")]
+/// Does not reallocate when the Vec's length is too large; the Vec is handed back inside the error instead.
+impl<T, SlimmerMetadata> TryFrom<Vec<T>> for SlimmerBox<[T], SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+{
+    type Error = PointerMetadataDoesNotFitError<[T], SlimmerMetadata, Vec<T>>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        let meta = ptr_meta::metadata(vec.as_slice());
+        if SlimmerMetadata::try_compress(meta).is_none() {
+            return Err(PointerMetadataDoesNotFitError::new(meta, vec));
+        }
+        // SAFETY: Checked above
+        Ok(unsafe { SlimmerBox::from_box_unchecked(vec.into_boxed_slice()) })
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Does not reallocate when the String's length is too large; the String is handed back inside the error instead.
+impl<SlimmerMetadata> TryFrom<String> for SlimmerBox<str, SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
+{
+    type Error = PointerMetadataDoesNotFitError<str, SlimmerMetadata, String>;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        let meta = ptr_meta::metadata(string.as_str());
+        if SlimmerMetadata::try_compress(meta).is_none() {
+            return Err(PointerMetadataDoesNotFitError::new(meta, string));
+        }
+        // SAFETY: Checked above
+        Ok(unsafe { SlimmerBox::from_box_unchecked(string.into_boxed_str()) })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> TryFrom<&[T]> for SlimmerBox<[T], SlimmerMetadata>
+where
+    T: Clone,
+    [T]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+{
+    type Error = PointerMetadataDoesNotFitError<[T], SlimmerMetadata>;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        SlimmerBox::try_new(slice)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<SlimmerMetadata> TryFrom<&str> for SlimmerBox<str, SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
+{
+    type Error = PointerMetadataDoesNotFitError<str, SlimmerMetadata>;
+
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        SlimmerBox::try_new(string)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<SlimmerMetadata> core::str::FromStr for SlimmerBox<str, SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
+{
+    type Err = PointerMetadataDoesNotFitError<str, SlimmerMetadata>;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        SlimmerBox::try_new(string)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, SlimmerMetadata> From<SlimmerBox<[T], SlimmerMetadata>> for Box<[T]>
+where
+    [T]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+{
+    fn from(boxed: SlimmerBox<[T], SlimmerMetadata>) -> Self {
+        SlimmerBox::into_box(boxed)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<SlimmerMetadata> From<SlimmerBox<str, SlimmerMetadata>> for Box<str>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
+{
+    fn from(boxed: SlimmerBox<str, SlimmerMetadata>) -> Self {
+        SlimmerBox::into_box(boxed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<SlimmerMetadata> From<SlimmerBox<::std::ffi::CStr, SlimmerMetadata>> for Box<::std::ffi::CStr>
+where
+    ::std::ffi::CStr: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<::std::ffi::CStr as Pointee>::Metadata>,
+{
+    fn from(boxed: SlimmerBox<::std::ffi::CStr, SlimmerMetadata>) -> Self {
+        SlimmerBox::into_box(boxed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<SlimmerMetadata> From<SlimmerBox<::std::ffi::OsStr, SlimmerMetadata>> for Box<::std::ffi::OsStr>
+where
+    ::std::ffi::OsStr: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<::std::ffi::OsStr as Pointee>::Metadata>,
+{
+    fn from(boxed: SlimmerBox<::std::ffi::OsStr, SlimmerMetadata>) -> Self {
+        SlimmerBox::into_box(boxed)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Collects into a Vec first, which is then turned into a SlimmerBox.
+///
+/// Panics if the number of elements is too large to fit in SlimmerMetadata.
+/// Collect into a Vec and use `SlimmerBox::try_from(vec)` to handle this case instead.
+impl<T, SlimmerMetadata> core::iter::FromIterator<T> for SlimmerBox<[T], SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let vec: Vec<T> = iter.into_iter().collect();
+        SlimmerBox::try_from(vec).map_err(|error| error.without_input()).unwrap()
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An empty slice, which does not allocate.
+impl<T, SlimmerMetadata> Default for SlimmerBox<[T], SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
+{
+    fn default() -> Self {
+        SlimmerBox::from_box(Box::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An empty string, which does not allocate.
+impl<SlimmerMetadata> Default for SlimmerBox<str, SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
+{
+    fn default() -> Self {
+        SlimmerBox::from_box(Box::default())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use crate::SlimmerBox;
 
@@ -704,20 +1798,376 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::let_unit_value)]
     fn zst() {
         let boxed_unit = SlimmerBox::new(&());
         println!("{:?}", boxed_unit);
         let _unit2 = *SlimmerBox::into_box(boxed_unit).clone();
     }
 
+    #[test]
+    fn default_metadata() {
+        #[derive(Debug, PartialEq)]
+        struct MyStruct(u64, u64);
+
+        let boxed: SlimmerBox<MyStruct> = SlimmerBox::from_value(MyStruct(1, 2));
+        assert_eq!(core::mem::size_of_val(&boxed), 8);
+        assert_eq!(*boxed, MyStruct(1, 2));
+
+        let boxed: SlimmerBox<[u8]> = SlimmerBox::new(&[1, 2, 3]);
+        assert_eq!(core::mem::size_of_val(&boxed), 12);
+        let boxed: SlimmerBox<str> = SlimmerBox::new("hello");
+        assert_eq!(core::mem::size_of_val(&boxed), 12);
+        // Overriding the default is still possible:
+        let boxed: SlimmerBox<str, u8> = SlimmerBox::new("hello");
+        assert_eq!(core::mem::size_of_val(&boxed), 9);
+    }
+
+    #[test]
+    fn from_array() {
+        use alloc::string::String;
+
+        let boxed: SlimmerBox<[String], u8> =
+            SlimmerBox::from_array([String::from("a"), String::from("b")]);
+        assert_eq!(&*boxed, &[String::from("a"), String::from("b")]);
+
+        let boxed: SlimmerBox<[u8], u8> = SlimmerBox::new_const_checked(&[7; 255]);
+        assert_eq!(boxed.len(), 255);
+
+        let empty: SlimmerBox<[u64], u8> = SlimmerBox::from_array([]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn uninit_and_zeroed_slices() {
+        let mut buffer: SlimmerBox<[_], u8> = SlimmerBox::new_uninit_slice(255);
+        for elem in buffer.iter_mut() {
+            elem.write(alloc::string::String::from("a"));
+        }
+        let buffer = unsafe { SlimmerBox::assume_init(buffer) };
+        assert!(buffer.iter().all(|elem| elem == "a"));
+
+        let zeroed: SlimmerBox<[_], u32> = SlimmerBox::new_zeroed_slice(1 << 20);
+        let zeroed: SlimmerBox<[u64], u32> = unsafe { SlimmerBox::assume_init(zeroed) };
+        assert_eq!(zeroed.len(), 1 << 20);
+        assert!(zeroed.iter().all(|&elem| elem == 0));
+
+        let empty: SlimmerBox<[core::mem::MaybeUninit<u8>], u8> = SlimmerBox::new_zeroed_slice(0);
+        assert!(empty.is_empty());
+
+        let error = SlimmerBox::<[core::mem::MaybeUninit<u8>], u8>::try_new_uninit_slice(256)
+            .unwrap_err();
+        assert_eq!(error.metadata(), 256);
+    }
+
+    #[test]
+    fn generators() {
+        use alloc::string::{String, ToString};
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let boxed: SlimmerBox<[String], u8> = SlimmerBox::from_fn(3, |index| index.to_string());
+        assert_eq!(&*boxed, &["0", "1", "2"]);
+
+        let boxed: SlimmerBox<[String], u8> = SlimmerBox::repeat(String::from("a"), 255);
+        assert!(boxed.iter().all(|elem| elem == "a"));
+        assert!(SlimmerBox::<[u8], u8>::try_from_fn(256, |_| unreachable!()).is_err());
+
+        let boxed: SlimmerBox<[u64], u8> = SlimmerBox::from_exact_size_iter(alloc::vec![1, 2, 3]);
+        assert_eq!(&*boxed, &[1, 2, 3]);
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct CountDrops;
+        impl Drop for CountDrops {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        let result = std::panic::catch_unwind(|| {
+            SlimmerBox::<[CountDrops], u8>::from_fn(10, |index| {
+                assert!(index < 4);
+                CountDrops
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn conversions() {
+        use alloc::boxed::Box;
+        use alloc::string::String;
+        use alloc::vec::Vec;
+
+        let boxed: SlimmerBox<[u8], u8> = SlimmerBox::try_from(alloc::vec![1, 2, 3]).unwrap();
+        assert_eq!(SlimmerBox::into_vec(boxed), alloc::vec![1, 2, 3]);
+        let error = SlimmerBox::<[u8], u8>::try_from(alloc::vec![0; 256]).unwrap_err();
+        assert_eq!(error.into_input().len(), 256);
+
+        let boxed: SlimmerBox<str, u8> = SlimmerBox::try_from(String::from("hello")).unwrap();
+        assert_eq!(SlimmerBox::into_string(boxed), "hello");
+        let boxed: SlimmerBox<str, u8> = "hello".parse().unwrap();
+        assert_eq!(&*boxed, "hello");
+        assert!("x".repeat(256).parse::<SlimmerBox<str, u8>>().is_err());
+        let boxed: SlimmerBox<str, u8> = SlimmerBox::try_from("hello").unwrap();
+        let boxed: Box<str> = boxed.into();
+        assert_eq!(&*boxed, "hello");
+
+        let boxed: SlimmerBox<[u64], u8> = SlimmerBox::try_from(&[1, 2][..]).unwrap();
+        assert_eq!(&*boxed, &[1, 2]);
+        let boxed: SlimmerBox<[u64], u8> = SlimmerBox::try_from(Box::from([1, 2])).unwrap();
+        let boxed: Box<[u64]> = boxed.into();
+        assert_eq!(&*boxed, &[1, 2]);
+
+        let collected: SlimmerBox<[u64]> = (0..5).collect();
+        assert_eq!(&*collected, &[0, 1, 2, 3, 4]);
+        assert_eq!(SlimmerBox::<[u64]>::default().len(), 0);
+        assert_eq!(&*SlimmerBox::<str>::default(), "");
+        let _: Vec<u64> = SlimmerBox::into_vec(collected);
+
+        let boxed: SlimmerBox<str, u8> = SlimmerBox::try_from(Box::<str>::from("hello")).unwrap();
+        assert_eq!(&*boxed, "hello");
+        #[cfg(feature = "std")]
+        {
+            use std::ffi::{CStr, CString, OsStr};
+
+            let boxed = CString::new("hello").unwrap().into_boxed_c_str();
+            let boxed: SlimmerBox<CStr, u8> = SlimmerBox::try_from(boxed).unwrap();
+            assert_eq!(boxed.to_bytes(), b"hello");
+            let boxed: Box<CStr> = Box::from(boxed);
+            assert_eq!(boxed.to_bytes(), b"hello");
+            let boxed: Box<OsStr> = Box::from(OsStr::new(&"x".repeat(256)));
+            let error = SlimmerBox::<OsStr, u8>::try_from(boxed).unwrap_err();
+            assert_eq!(error.into_input().len(), 256);
+            let boxed: SlimmerBox<OsStr> = SlimmerBox::from_box(Box::from(OsStr::new("hello")));
+            let boxed: Box<OsStr> = Box::from(boxed);
+            assert_eq!(&*boxed, OsStr::new("hello"));
+        }
+    }
+
+    #[test]
+    fn by_value() {
+        use alloc::string::String;
+
+        // Neither Clone nor Unpin:
+        struct Resource(String, core::marker::PhantomPinned);
+
+        let boxed: SlimmerBox<Resource, ()> =
+            SlimmerBox::from_value(Resource(String::from("hello"), core::marker::PhantomPinned));
+        assert_eq!(core::mem::size_of_val(&boxed), 8);
+        assert_eq!(boxed.0, "hello");
+        let resource = SlimmerBox::into_inner(boxed);
+        assert_eq!(resource.0, "hello");
+
+        let pinned = SlimmerBox::<Resource, ()>::pin(resource);
+        assert_eq!(pinned.as_ref().get_ref().0, "hello");
+
+        let unit: SlimmerBox<(), ()> = SlimmerBox::from_value(());
+        SlimmerBox::into_inner(unit);
+    }
+
     #[test]
     fn empty_slice() {
         let boxed_slice: SlimmerBox<[u64]> = SlimmerBox::new(&[]);
         assert_eq!(core::mem::size_of_val(&boxed_slice), 12);
         println!("{:?}", boxed_slice);
+
+        // Not pointing into the borrowed input, but dangling (while properly aligned):
+        let input: [u64; 4] = [1, 2, 3, 4];
+        let boxed_slice: SlimmerBox<[u64]> = SlimmerBox::new(&input[..0]);
+        let ptr = SlimmerBox::to_ptr(&boxed_slice) as *const u64;
+        assert_ne!(ptr, input.as_ptr());
+        assert_eq!(ptr as usize, core::mem::align_of::<u64>());
+    }
+
+    #[test]
+    fn copied() {
+        let input: [u64; 4] = [1, 2, 3, 4];
+        let boxed_slice: SlimmerBox<[u64], u8> = SlimmerBox::new_copied(&input[..]);
+        assert_eq!(&*boxed_slice, &input);
+        assert!(SlimmerBox::<[u64], u8>::try_new_copied(&[0; 256][..]).is_err());
+
+        let empty: SlimmerBox<[u64]> = SlimmerBox::new_copied(&input[..0]);
+        let ptr = SlimmerBox::to_ptr(&empty) as *const u64;
+        assert_eq!(ptr as usize, core::mem::align_of::<u64>());
+
+        let boxed_str: SlimmerBox<str, u8> = SlimmerBox::new_copied("hello");
+        assert_eq!(&*boxed_str, "hello");
+        let boxed_value: SlimmerBox<u32, ()> = SlimmerBox::new_copied(&42);
+        assert_eq!(*boxed_value, 42);
+    }
+
+    #[test]
+    fn failure_hands_back_input() {
+        use crate::{SlimmerRc, ThinSlimmerBox};
+        use alloc::boxed::Box;
+        use alloc::string::ToString;
+
+        let boxed: Box<[u8]> = alloc::vec![42; 300].into_boxed_slice();
+        let error = SlimmerBox::<[u8], u8>::try_from_box(boxed).unwrap_err();
+        assert_eq!(error.metadata(), 300);
+        assert_eq!(error.input().len(), 300);
+        // The caller can fall back to a wider representation:
+        let boxed: SlimmerBox<[u8], u16> = SlimmerBox::from_box(error.into_input());
+        assert_eq!(&*boxed, &[42; 300][..]);
+
+        let thin = ThinSlimmerBox::from_box(SlimmerBox::into_box(boxed));
+        let error = ThinSlimmerBox::try_into_slimmer_box::<u8>(thin).unwrap_err();
+        assert_eq!(&*error.into_input(), &[42; 300][..]);
+
+        let error = SlimmerRc::<str, u8>::try_from_box("x".repeat(256).into_boxed_str()).unwrap_err();
+        assert_eq!(error.metadata(), 256);
+        assert_eq!(error.into_input().len(), 256);
+
+        let error = SlimmerBox::<[u64], u8>::try_new(&[0; 256]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Pointer Metadata usize (8 bytes) could not be converted to u8 (1 bytes)"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_impl() {
+        fn assert_error<E: std::error::Error + Send + Sync + 'static>(_: &E) {}
+        assert_error(&SlimmerBox::<[u64], u8>::try_new(&[0; 256]).unwrap_err());
+        assert_error(&SlimmerBox::<[u64], u8>::try_from_box(alloc::vec![0; 256].into()).unwrap_err());
+    }
+
+    #[test]
+    fn fallible_allocation() {
+        use crate::AllocError;
+        use allocator_api2::alloc::{AllocError as AllocatorError, Allocator};
+        use core::alloc::Layout;
+        use core::ptr::NonNull;
+
+        struct FailingAlloc;
+        unsafe impl Allocator for FailingAlloc {
+            fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocatorError> {
+                Err(AllocatorError)
+            }
+            unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+                unreachable!()
+            }
+        }
+
+        let result = SlimmerBox::<[u64], u32, _>::try_new_fallible_in(&[1, 2, 3], FailingAlloc);
+        match result {
+            Err(AllocError::AllocationFailed(layout)) => {
+                assert_eq!(layout, Layout::new::<[u64; 3]>())
+            }
+            _ => panic!("expected the allocation to fail"),
+        }
+
+        // The metadata is checked before allocating:
+        let result = SlimmerBox::<[u64], u8, _>::try_new_fallible_in(&[0; 256], FailingAlloc);
+        assert!(matches!(result, Err(AllocError::MetadataDoesNotFit(_))));
+
+        // Nothing needs to be allocated for empty values:
+        let empty = SlimmerBox::<[u64], u32, _>::try_new_fallible_in(&[], FailingAlloc).unwrap();
+        assert!(empty.is_empty());
+
+        let boxed = SlimmerBox::<[u64]>::try_new_fallible(&[1, 2, 3]).unwrap();
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn panicking_clone_frees_allocation() {
+        use allocator_api2::alloc::{AllocError, Allocator, Global};
+        use core::alloc::Layout;
+        use core::ptr::NonNull;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static LIVE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone, Copy)]
+        struct CountingAlloc;
+        unsafe impl Allocator for CountingAlloc {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                LIVE_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+                Global.allocate(layout)
+            }
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                LIVE_ALLOCATIONS.fetch_sub(1, Ordering::SeqCst);
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        struct PanicOnClone;
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                panic!("cloning panics")
+            }
+        }
+
+        let input = [PanicOnClone, PanicOnClone];
+        let result = std::panic::catch_unwind(|| {
+            SlimmerBox::<[PanicOnClone], u32, CountingAlloc>::new_in(&input, CountingAlloc)
+        });
+        assert!(result.is_err());
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 0);
+
+        struct PanicOnDrop(#[allow(dead_code)] u64);
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                panic!("dropping panics")
+            }
+        }
+
+        let boxed: SlimmerBox<PanicOnDrop, (), CountingAlloc> = SlimmerBox::from_box_in(
+            allocator_api2::boxed::Box::new_in(PanicOnDrop(0), CountingAlloc),
+        );
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 1);
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| drop(boxed)));
+        assert!(result.is_err());
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn custom_allocator() {
+        use allocator_api2::alloc::{AllocError, Allocator, Global};
+        use core::alloc::Layout;
+        use core::ptr::NonNull;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static LIVE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone, Copy)]
+        struct CountingAlloc;
+        unsafe impl Allocator for CountingAlloc {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                LIVE_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+                Global.allocate(layout)
+            }
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                LIVE_ALLOCATIONS.fetch_sub(1, Ordering::SeqCst);
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        let boxed: SlimmerBox<str, u32, CountingAlloc> = SlimmerBox::new_in("hello", CountingAlloc);
+        assert_eq!(core::mem::size_of_val(&boxed), 12);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 1);
+
+        let cloned = boxed.clone();
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 2);
+        drop(cloned);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 1);
+
+        let std_box = SlimmerBox::into_box_in(boxed);
+        assert_eq!(&*std_box, "hello");
+        let boxed: SlimmerBox<str, u8, CountingAlloc> = SlimmerBox::from_box_in(std_box);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 1);
+        drop(boxed);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 0);
+
+        let empty: SlimmerBox<[u64], u32, CountingAlloc> = SlimmerBox::new_in(&[], CountingAlloc);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 0);
+        drop(empty);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::SeqCst), 0);
     }
 
     #[test]
+    #[allow(clippy::borrow_deref_ref)]
     fn compact_str_example() {
         pub enum CompactStr {
             Small{buffer: [u8; 14], len: u8}, // <- Or, using the `modular_bitfield` crate, this could even be { buffer: [u8; 15], len: u4} !
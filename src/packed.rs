@@ -0,0 +1,484 @@
+//! A single-word box for slices and strings whose length is stored in the unused high bits of the pointer.
+//!
+//! See [`PackedSlimmerBox<T>`] for more information.
+use alloc::boxed::Box;
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+use ptr_meta::Pointee;
+
+use crate::{CloneUnsized, SlimmerBox, SlimmerPointee};
+
+/// The number of low bits of a pointer that are used for the address.
+const ADDRESS_BITS: u32 = 48;
+const ADDRESS_MASK: usize = (1 << ADDRESS_BITS) - 1;
+
+/// An alternative to [`SlimmerBox<T, u16>`] for slices and strings which is exactly one word in size,
+/// by storing the length in the unused high bits of the pointer itself.
+///
+/// On x86_64 and aarch64, user space addresses only use the lower 48 bits of a pointer.
+/// This leaves the 16 high bits free to store a `u16` length.
+/// A `PackedSlimmerBox` is therefore 8 bytes, 8-aligned, and (as the address is never null)
+/// `Option<PackedSlimmerBox<T>>` is 8 bytes as well.
+/// Unlike [`ThinSlimmerBox<T>`](crate::ThinSlimmerBox), reading the length does not require an extra memory access.
+///
+/// ```rust
+/// use slimmer_box::{PackedSlimmerBox, SlimmerBox};
+///
+/// let packed: PackedSlimmerBox<str> = PackedSlimmerBox::new("hello, world!");
+/// assert_eq!(core::mem::size_of_val(&packed), 8);
+/// assert_eq!(core::mem::size_of::<Option<PackedSlimmerBox<[u64]>>>(), 8);
+/// assert_eq!(&*packed, "hello, world!");
+///
+/// let slimmer: SlimmerBox<str, u16> = PackedSlimmerBox::into_slimmer_box(packed);
+/// assert_eq!(&*slimmer, "hello, world!");
+/// ```
+///
+/// Whether an address fits is checked at runtime when constructing a PackedSlimmerBox,
+/// since some platforms (for instance those with 5-level paging) might hand out larger addresses.
+/// In that case (as well as when the length does not fit in a `u16`), a [`PackError`] is returned.
+///
+/// Only available on 64-bit targets.
+pub struct PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    // Stores `address | (len << ADDRESS_BITS)`
+    ptr: NonNull<()>,
+    marker: PhantomData<T>,
+}
+
+/// The error returned when a value cannot be stored in a [`PackedSlimmerBox`].
+///
+/// Fallible constructors that consume their input (like [`PackedSlimmerBox::try_from_box`]) hand it back inside the error,
+/// so it is not lost and the caller can fall back to another representation.
+/// For those that do not consume their input, `Input` is `()`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PackError<Input = ()> {
+    /// The length of the value does not fit in a `u16`.
+    MetadataDoesNotFit(Input),
+    /// The address of the value uses more than the lower 48 bits.
+    AddressDoesNotFit(Input),
+}
+
+impl<Input> PackError<Input> {
+    /// Takes back the input which could not be packed.
+    pub fn into_input(self) -> Input {
+        match self {
+            PackError::MetadataDoesNotFit(input) | PackError::AddressDoesNotFit(input) => input,
+        }
+    }
+
+    /// Drops the input, leaving only the description of what went wrong.
+    pub fn without_input(self) -> PackError {
+        self.with_input(())
+    }
+
+    fn with_input<Other>(self, input: Other) -> PackError<Other> {
+        match self {
+            PackError::MetadataDoesNotFit(_) => PackError::MetadataDoesNotFit(input),
+            PackError::AddressDoesNotFit(_) => PackError::AddressDoesNotFit(input),
+        }
+    }
+}
+
+impl<Input> core::fmt::Debug for PackError<Input> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            PackError::MetadataDoesNotFit(_) => f.write_str("MetadataDoesNotFit(..)"),
+            PackError::AddressDoesNotFit(_) => f.write_str("AddressDoesNotFit(..)"),
+        }
+    }
+}
+
+impl<Input> core::fmt::Display for PackError<Input> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            PackError::MetadataDoesNotFit(_) => write!(
+                f,
+                "Pointer metadata of type usize does not fit in the 16 high bits of a PackedSlimmerBox"
+            ),
+            PackError::AddressDoesNotFit(_) => write!(
+                f,
+                "Address does not fit in the {} low bits of a PackedSlimmerBox",
+                ADDRESS_BITS
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Input> std::error::Error for PackError<Input> {}
+
+impl<T> PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    /// Creates a new PackedSlimmerBox from the given slice or string.
+    ///
+    /// This involves cloning the slice (which will clone all elements one by one)
+    /// and as such only works for types whose contents are cloneable.
+    /// Otherwise, use `from_box`.
+    ///
+    /// Panics if the value's length does not fit in a u16, or if the address of the allocation does not fit in 48 bits.
+    pub fn new(value: &T) -> Self
+    where
+        T: CloneUnsized,
+        T: SlimmerPointee<u16>,
+    {
+        Self::try_new(value).unwrap()
+    }
+
+    /// Variant of `new` which will return an error if the value cannot be packed instead of panicing.
+    pub fn try_new(value: &T) -> Result<Self, PackError>
+    where
+        T: CloneUnsized,
+        T: SlimmerPointee<u16>,
+    {
+        let boxed: SlimmerBox<T, u16> =
+            SlimmerBox::try_new(value).map_err(|_| PackError::MetadataDoesNotFit(()))?;
+        Self::try_from_slimmer_box(boxed).map_err(PackError::without_input)
+    }
+
+    /// Turns a Box into a PackedSlimmerBox.
+    ///
+    /// This is a fast constant-time operation that needs no allocation, as it consumes the box.
+    ///
+    /// Panics if the value's length does not fit in a u16, or if the address does not fit in 48 bits.
+    pub fn from_box(boxed: Box<T>) -> Self {
+        Self::try_from_box(boxed).unwrap()
+    }
+
+    /// Variant of `from_box` which will return an error if the value cannot be packed instead of panicing.
+    ///
+    /// On error, the box is handed back inside the error.
+    pub fn try_from_box(boxed: Box<T>) -> Result<Self, PackError<Box<T>>> {
+        let raw = Box::into_raw(boxed);
+        match pack(raw) {
+            Ok(ptr) => Ok(Self {
+                ptr,
+                marker: PhantomData,
+            }),
+            // SAFETY: Obtained from Box::into_raw just above
+            Err(error) => Err(error.with_input(unsafe { Box::from_raw(raw) })),
+        }
+    }
+
+    /// Turns a PackedSlimmerBox into a Box.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_box(this: Self) -> Box<T> {
+        let ptr = PackedSlimmerBox::to_ptr(&this) as *mut T;
+        core::mem::forget(this);
+        // SAFETY: We reconstruct using the inverse operations from construction
+        unsafe { Box::from_raw(ptr) }
+    }
+
+    /// Turns a SlimmerBox into a PackedSlimmerBox.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    ///
+    /// Panics if the address does not fit in 48 bits.
+    /// (The length of a `SlimmerBox<T, u16>` always fits.)
+    pub fn from_slimmer_box(boxed: SlimmerBox<T, u16>) -> Self
+    where
+        T: SlimmerPointee<u16>,
+    {
+        Self::try_from_slimmer_box(boxed).unwrap()
+    }
+
+    /// Variant of `from_slimmer_box` which will return an error if the address does not fit instead of panicing.
+    ///
+    /// On error, the SlimmerBox is handed back inside the error.
+    pub fn try_from_slimmer_box(
+        boxed: SlimmerBox<T, u16>,
+    ) -> Result<Self, PackError<SlimmerBox<T, u16>>>
+    where
+        T: SlimmerPointee<u16>,
+    {
+        let ptr = match pack(SlimmerBox::to_ptr(&boxed) as *mut T) {
+            Ok(ptr) => ptr,
+            Err(error) => return Err(error.with_input(boxed)),
+        };
+        let _ = SlimmerBox::into_raw(boxed);
+        Ok(Self {
+            ptr,
+            marker: PhantomData,
+        })
+    }
+
+    /// Turns a PackedSlimmerBox into a SlimmerBox.
+    ///
+    /// This is a fast constant-time operation that needs no allocation, and never fails.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_slimmer_box(this: Self) -> SlimmerBox<T, u16>
+    where
+        T: SlimmerPointee<u16>,
+    {
+        // SAFETY: The length was checked to fit in a u16 on construction
+        unsafe { SlimmerBox::from_box_unchecked(PackedSlimmerBox::into_box(this)) }
+    }
+
+    /// Obtains a raw read-only (non-owned) pointer view of the contents of this PackedSlimmerBox.
+    ///
+    /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        let len = PackedSlimmerBox::metadata(this);
+        // NOTE: Using pointer arithmetic rather than integer casts keeps the pointer's provenance intact.
+        let address = (this.ptr.as_ptr() as *mut u8).wrapping_sub(len << ADDRESS_BITS);
+        ptr_meta::from_raw_parts(address as *const (), len)
+    }
+
+    /// Returns the length stored in the high bits of the pointer.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> usize {
+        (this.ptr.as_ptr() as usize) >> ADDRESS_BITS
+    }
+}
+
+/// Packs the length of `ptr` into its high bits, if both the length and the address fit.
+fn pack<T>(ptr: *mut T) -> Result<NonNull<()>, PackError>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    let (address, len) = ptr_meta::PtrExt::to_raw_parts(ptr);
+    if len > u16::MAX as usize {
+        return Err(PackError::MetadataDoesNotFit(()));
+    }
+    if address as usize & !ADDRESS_MASK != 0 {
+        return Err(PackError::AddressDoesNotFit(()));
+    }
+    // NOTE: Since the high bits of the address are zero, this addition is the same as a bitwise OR.
+    let tagged = (address as *mut u8).wrapping_add(len << ADDRESS_BITS);
+    // SAFETY: Boxes are never null, and adding to the high bits keeps it that way
+    Ok(unsafe { NonNull::new_unchecked(tagged as *mut ()) })
+}
+
+unsafe impl<T: Send> Send for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+unsafe impl<T: Sync> Sync for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+impl<T> Drop for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn drop(&mut self) {
+        let ptr = PackedSlimmerBox::to_ptr(self) as *mut T;
+        // SAFETY: The pointer was obtained from a Box on construction
+        core::mem::drop(unsafe { Box::from_raw(ptr) });
+    }
+}
+
+impl<T> Deref for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Correct by construction
+        unsafe { &*PackedSlimmerBox::to_ptr(self) }
+    }
+}
+
+impl<T> DerefMut for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: Correct by construction, and we are borrowed mutably
+        unsafe { &mut *(PackedSlimmerBox::to_ptr(self) as *mut T) }
+    }
+}
+
+impl<T> core::borrow::Borrow<T> for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T> core::borrow::BorrowMut<T> for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T> AsRef<T> for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T> AsMut<T> for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T> Unpin for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+impl<T> Clone for PackedSlimmerBox<T>
+where
+    T: CloneUnsized,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<u16>,
+{
+    /// Panics in the (unlikely) case that the address of the clone does not fit in 48 bits.
+    fn clone(&self) -> Self {
+        PackedSlimmerBox::new(self.deref())
+    }
+}
+
+impl<T> core::fmt::Debug for PackedSlimmerBox<T>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord> Ord for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: Eq> Eq for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for PackedSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<PackedSlimmerBox<str>>(), 8);
+        assert_eq!(core::mem::align_of::<PackedSlimmerBox<str>>(), 8);
+        assert_eq!(core::mem::size_of::<Option<PackedSlimmerBox<[u64]>>>(), 8);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let packed: PackedSlimmerBox<[u64]> = PackedSlimmerBox::new(&[1, 2, 3][..]);
+        assert_eq!(PackedSlimmerBox::metadata(&packed), 3);
+        let slimmer: SlimmerBox<[u64], u16> = PackedSlimmerBox::into_slimmer_box(packed.clone());
+        let ptr = SlimmerBox::to_ptr(&slimmer);
+        assert_eq!(&*slimmer, &*packed);
+        let packed2 = PackedSlimmerBox::from_slimmer_box(slimmer);
+        assert_eq!(PackedSlimmerBox::to_ptr(&packed2), ptr);
+        assert_eq!(packed, packed2);
+        let boxed = PackedSlimmerBox::into_box(packed2);
+        assert_eq!(&*boxed, &[1, 2, 3]);
+
+        let empty: PackedSlimmerBox<str> = PackedSlimmerBox::new("");
+        assert_eq!(&*empty, "");
+        let max: PackedSlimmerBox<[u8]> = PackedSlimmerBox::new(&[7; 65535][..]);
+        assert_eq!(max.len(), 65535);
+        assert!(max.iter().all(|&byte| byte == 7));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            PackedSlimmerBox::<[u8]>::try_new(&[0; 65536][..]),
+            Err(PackError::MetadataDoesNotFit(()))
+        );
+        let high_address: *mut [u8] =
+            ptr_meta::from_raw_parts_mut(0xFFFF_0000_0000_0008 as *mut (), 1);
+        assert_eq!(pack(high_address), Err(PackError::AddressDoesNotFit(())));
+
+        let boxed: Box<[u8]> = alloc::vec![1; 65536].into_boxed_slice();
+        let error = PackedSlimmerBox::try_from_box(boxed).unwrap_err();
+        assert!(matches!(error, PackError::MetadataDoesNotFit(_)));
+        assert_eq!(error.into_input().len(), 65536);
+    }
+}
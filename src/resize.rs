@@ -0,0 +1,474 @@
+//! In-place resizing of `SlimmerBox<[T]>` and `SlimmerBox<str>`.
+//!
+//! A SlimmerBox has no spare capacity, so every change in length reallocates to the exact new size
+//! and stores the new length in the (packed) metadata.
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+use ptr_meta::Pointee;
+
+use crate::clone_unsized::PartialSliceGuard;
+use crate::{PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox, SlimmerPointee};
+
+/// Reallocates an allocation of `old_len` elements into one of `new_len` elements.
+///
+/// Elements in `0..min(old_len, new_len)` are kept, the others are neither dropped nor initialized.
+///
+/// # Safety
+/// `ptr` needs to have been allocated by the global allocator with room for exactly `old_len` elements
+/// (or be dangling if that is zero-sized).
+unsafe fn realloc_slice<T>(ptr: *mut T, old_len: usize, new_len: usize) -> *mut T {
+    let old_layout = Layout::array::<T>(old_len).unwrap_unchecked();
+    let new_layout = Layout::array::<T>(new_len).expect("allocation size overflow");
+    let new_ptr = match (old_layout.size(), new_layout.size()) {
+        (0, 0) => return ptr,
+        (_, 0) => {
+            alloc::alloc::dealloc(ptr as *mut u8, old_layout);
+            return new_layout.align() as *mut T;
+        }
+        (0, _) => alloc::alloc::alloc(new_layout),
+        (_, new_size) => alloc::alloc::realloc(ptr as *mut u8, old_layout, new_size),
+    };
+    if new_ptr.is_null() {
+        alloc::alloc::handle_alloc_error(new_layout)
+    }
+    new_ptr as *mut T
+}
+
+impl<T, SlimmerMetadata> SlimmerBox<[T], SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Shortens the slice to `len` elements, dropping the rest and shrinking the allocation to fit.
+    ///
+    /// Does nothing if `len` is greater than or equal to the current length.
+    ///
+    /// As `SlimmerBox<str>` has a `truncate` as well, the pointee needs to be named when calling it:
+    /// `SlimmerBox::<[T], _>::truncate(&mut boxed, len)`.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn truncate(this: &mut Self, len: usize) {
+        let old_len = SlimmerBox::metadata(this);
+        if len >= old_len {
+            return;
+        }
+        let ptr = this.ptr.as_ptr() as *mut T;
+        // NOTE: Shrinks the allocation even if dropping one of the elements panics.
+        let _guard = Compact {
+            this,
+            old_len,
+            read: old_len,
+            write: len,
+        };
+        // SAFETY: These elements are initialized and no longer reachable once the guard shrinks the allocation
+        unsafe {
+            core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+                ptr.add(len),
+                old_len - len,
+            ))
+        };
+    }
+
+    /// Retains only the elements for which `f` returns true, shrinking the allocation to fit.
+    ///
+    /// The order of the retained elements is preserved.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn retain<F>(this: &mut Self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let old_len = SlimmerBox::metadata(this);
+        let ptr = this.ptr.as_ptr() as *mut T;
+        let mut guard = Compact {
+            this,
+            old_len,
+            read: 0,
+            write: 0,
+        };
+        while guard.read < old_len {
+            // SAFETY: Elements in read..old_len are initialized and not yet moved
+            unsafe {
+                let current = ptr.add(guard.read);
+                // NOTE: `read` is only advanced after calling `f`, so the current element is kept if it panics
+                if f(&*current) {
+                    core::ptr::copy(current, ptr.add(guard.write), 1);
+                    guard.read += 1;
+                    guard.write += 1;
+                } else {
+                    guard.read += 1;
+                    core::ptr::drop_in_place(current);
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive repeated elements, shrinking the allocation to fit.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn dedup(this: &mut Self)
+    where
+        T: PartialEq,
+    {
+        SlimmerBox::dedup_by(this, |a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements for which `same_bucket` returns true,
+    /// shrinking the allocation to fit.
+    ///
+    /// Just like [`Vec::dedup_by`](alloc::vec::Vec::dedup_by), `same_bucket` is called with the element under consideration
+    /// and the last element that was kept, in that order.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn dedup_by<F>(this: &mut Self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let old_len = SlimmerBox::metadata(this);
+        if old_len <= 1 {
+            return;
+        }
+        let ptr = this.ptr.as_ptr() as *mut T;
+        let mut guard = Compact {
+            this,
+            old_len,
+            read: 1,
+            write: 1,
+        };
+        while guard.read < old_len {
+            // SAFETY: Elements in 0..write and read..old_len are initialized, and write <= read
+            unsafe {
+                let current = ptr.add(guard.read);
+                let previous = ptr.add(guard.write - 1);
+                // NOTE: `read` is only advanced after calling `same_bucket`, so the current element is kept if it panics
+                if same_bucket(&mut *current, &mut *previous) {
+                    guard.read += 1;
+                    core::ptr::drop_in_place(current);
+                } else {
+                    core::ptr::copy(current, ptr.add(guard.write), 1);
+                    guard.read += 1;
+                    guard.write += 1;
+                }
+            }
+        }
+    }
+
+    /// Appends clones of all elements in `other`, growing the allocation to fit.
+    ///
+    /// Panics if the new length is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn extend_from_slice(this: &mut Self, other: &[T])
+    where
+        T: Clone,
+    {
+        SlimmerBox::try_extend_from_slice(this, other).unwrap()
+    }
+
+    /// Variant of `extend_from_slice` which will return an error if the new length is too large instead of panicing.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn try_extend_from_slice(
+        this: &mut Self,
+        other: &[T],
+    ) -> Result<(), PointerMetadataDoesNotFitError<[T], SlimmerMetadata>>
+    where
+        T: Clone,
+    {
+        SlimmerBox::try_grow_with(this, other.len(), |index| other[index].clone())
+    }
+
+    /// Resizes the slice to `new_len` elements, (re)allocating to fit.
+    ///
+    /// If the slice grows, the new elements are clones of `value`.
+    /// If the slice shrinks, this behaves like `truncate`.
+    ///
+    /// Panics if `new_len` is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn resize(this: &mut Self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        SlimmerBox::try_resize(this, new_len, value).unwrap()
+    }
+
+    /// Variant of `resize` which will return an error if `new_len` is too large instead of panicing.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn try_resize(
+        this: &mut Self,
+        new_len: usize,
+        value: T,
+    ) -> Result<(), PointerMetadataDoesNotFitError<[T], SlimmerMetadata>>
+    where
+        T: Clone,
+    {
+        let old_len = SlimmerBox::metadata(this);
+        if new_len <= old_len {
+            Self::truncate(this, new_len);
+            return Ok(());
+        }
+        SlimmerBox::try_grow_with(this, new_len - old_len, |_| value.clone())
+    }
+
+    /// Grows the allocation by `additional` elements, which are created by calling `fill` with their index.
+    ///
+    /// If `fill` panics, the elements created so far are dropped and the allocation shrinks back.
+    fn try_grow_with<F>(
+        this: &mut Self,
+        additional: usize,
+        mut fill: F,
+    ) -> Result<(), PointerMetadataDoesNotFitError<[T], SlimmerMetadata>>
+    where
+        F: FnMut(usize) -> T,
+    {
+        let old_len = SlimmerBox::metadata(this);
+        let new_len = old_len.checked_add(additional).expect("capacity overflow");
+        let slim_meta = SlimmerMetadata::try_compress(new_len)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(new_len, ()))?;
+
+        // SAFETY: The allocation has room for exactly `old_len` elements
+        let ptr = unsafe { realloc_slice(this.ptr.as_ptr() as *mut T, old_len, new_len) };
+        // SAFETY: Returned by realloc_slice, never null
+        this.ptr = unsafe { NonNull::new_unchecked(ptr as *mut ()) };
+
+        // NOTE: Dropped in reverse order on unwind, so the new elements are dropped before the allocation shrinks back.
+        let shrink_guard = Compact {
+            this,
+            old_len: new_len,
+            read: new_len,
+            write: old_len,
+        };
+        let mut guard = PartialSliceGuard {
+            // SAFETY: old_len <= new_len
+            dest: unsafe { ptr.add(old_len) },
+            initialized: 0,
+        };
+        while guard.initialized < additional {
+            // SAFETY: There is room for `additional` elements after the existing ones
+            unsafe {
+                guard
+                    .dest
+                    .add(guard.initialized)
+                    .write(fill(guard.initialized))
+            };
+            guard.initialized += 1;
+        }
+        core::mem::forget(guard);
+        core::mem::forget(shrink_guard);
+
+        this.meta = slim_meta;
+        Ok(())
+    }
+}
+
+/// Moves the elements in `read..old_len` to `write..`, then shrinks the allocation to fit the remaining elements.
+///
+/// This happens when the guard is dropped, so also when dropping an element (or a user-provided closure) panics.
+/// The elements in `write..read` need to have been dropped (or moved out) by then.
+///
+/// Dropping the guard never panics: `old_len` was stored in the SlimmerMetadata before,
+/// so the (smaller) new length is guaranteed to fit by the safety contract of [`SlimMetadata`].
+struct Compact<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    this: &'a mut SlimmerBox<[T], SlimmerMetadata>,
+    old_len: usize,
+    read: usize,
+    write: usize,
+}
+
+impl<'a, T, SlimmerMetadata> Drop for Compact<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn drop(&mut self) {
+        let ptr = self.this.ptr.as_ptr() as *mut T;
+        let tail_len = self.old_len - self.read;
+        let new_len = self.write + tail_len;
+        unsafe {
+            // SAFETY: new_len <= old_len <= SlimmerMetadata::MAX, and every such length can be compressed
+            let slim_meta = SlimmerMetadata::try_compress(new_len).unwrap_unchecked();
+            // SAFETY: The tail is initialized, and write <= read
+            core::ptr::copy(ptr.add(self.read), ptr.add(self.write), tail_len);
+            // SAFETY: The allocation has room for exactly `old_len` elements
+            let ptr = realloc_slice(ptr, self.old_len, new_len);
+            self.this.ptr = NonNull::new_unchecked(ptr as *mut ());
+            self.this.meta = slim_meta;
+        }
+    }
+}
+
+impl<SlimmerMetadata> SlimmerBox<str, SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Shortens the string to `len` bytes, shrinking the allocation to fit.
+    ///
+    /// Does nothing if `len` is greater than or equal to the current length.
+    ///
+    /// Panics if `len` does not lie on a char boundary.
+    ///
+    /// As `SlimmerBox<[T]>` has a `truncate` as well, the pointee needs to be named when calling it:
+    /// `SlimmerBox::<str, _>::truncate(&mut boxed, len)`.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn truncate(this: &mut Self, len: usize) {
+        let old_len = SlimmerBox::metadata(this);
+        if len >= old_len {
+            return;
+        }
+        assert!(
+            this.is_char_boundary(len),
+            "new length does not lie on a char boundary"
+        );
+        let slim_meta = SlimmerMetadata::try_compress(len).expect(
+            "SlimmerMetadata should be able to store any length smaller than the current one",
+        );
+        // SAFETY: The allocation has room for exactly `old_len` bytes, and cutting at a char boundary keeps valid UTF-8
+        unsafe {
+            let ptr = realloc_slice(this.ptr.as_ptr() as *mut u8, old_len, len);
+            this.ptr = NonNull::new_unchecked(ptr as *mut ());
+        }
+        this.meta = slim_meta;
+    }
+
+    /// Appends `string`, growing the allocation to fit.
+    ///
+    /// Panics if the new length is too large to fit in SlimmerMetadata. This is checked before anything is allocated.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn push_str(this: &mut Self, string: &str) {
+        SlimmerBox::try_push_str(this, string).unwrap()
+    }
+
+    /// Variant of `push_str` which will return an error if the new length is too large instead of panicing.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn try_push_str(
+        this: &mut Self,
+        string: &str,
+    ) -> Result<(), PointerMetadataDoesNotFitError<str, SlimmerMetadata>> {
+        let old_len = SlimmerBox::metadata(this);
+        let new_len = old_len
+            .checked_add(string.len())
+            .expect("capacity overflow");
+        let slim_meta = SlimmerMetadata::try_compress(new_len)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(new_len, ()))?;
+        // SAFETY: The allocation has room for exactly `old_len` bytes, and appending valid UTF-8 keeps valid UTF-8
+        unsafe {
+            let ptr = realloc_slice(this.ptr.as_ptr() as *mut u8, old_len, new_len);
+            core::ptr::copy_nonoverlapping(string.as_ptr(), ptr.add(old_len), string.len());
+            this.ptr = NonNull::new_unchecked(ptr as *mut ());
+        }
+        this.meta = slim_meta;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn shrink_in_place() {
+        let mut boxed: SlimmerBox<[String], u8> =
+            SlimmerBox::from_fn(10, |index| index.to_string());
+        SlimmerBox::<[_], _>::truncate(&mut boxed, 8);
+        assert_eq!(boxed.len(), 8);
+        SlimmerBox::retain(&mut boxed, |elem| elem.parse::<u32>().unwrap() % 2 == 0);
+        assert_eq!(&*boxed, &["0", "2", "4", "6"]);
+        SlimmerBox::<[_], _>::truncate(&mut boxed, 0);
+        assert!(boxed.is_empty());
+
+        let mut boxed: SlimmerBox<[u64], u8> = SlimmerBox::from_array([1, 1, 2, 3, 3, 3, 1]);
+        SlimmerBox::dedup(&mut boxed);
+        assert_eq!(&*boxed, &[1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn grow_in_place() {
+        let mut boxed: SlimmerBox<[String], u8> = SlimmerBox::default();
+        SlimmerBox::extend_from_slice(&mut boxed, &[String::from("a"), String::from("b")]);
+        SlimmerBox::resize(&mut boxed, 4, String::from("c"));
+        assert_eq!(&*boxed, &["a", "b", "c", "c"]);
+        SlimmerBox::resize(&mut boxed, 1, String::from("d"));
+        assert_eq!(&*boxed, &["a"]);
+
+        let error = SlimmerBox::try_resize(&mut boxed, 256, String::new()).unwrap_err();
+        assert_eq!(error.metadata(), 256);
+        assert_eq!(&*boxed, &["a"]);
+
+        let mut zsts: SlimmerBox<[()], u8> = SlimmerBox::default();
+        SlimmerBox::resize(&mut zsts, 255, ());
+        assert_eq!(zsts.len(), 255);
+    }
+
+    #[test]
+    fn panicking_clone_shrinks_back() {
+        #[derive(Debug, PartialEq)]
+        struct PanicOnClone(u8);
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                assert!(self.0 < 2);
+                PanicOnClone(self.0)
+            }
+        }
+
+        let mut boxed: SlimmerBox<[PanicOnClone], u8> = SlimmerBox::from_array([PanicOnClone(0)]);
+        let other = [PanicOnClone(1), PanicOnClone(2)];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            SlimmerBox::extend_from_slice(&mut boxed, &other)
+        }));
+        assert!(result.is_err());
+        assert_eq!(&*boxed, &[PanicOnClone(0)]);
+    }
+
+    #[test]
+    fn panicking_predicate_keeps_element() {
+        let mut boxed: SlimmerBox<[String], u8> = SlimmerBox::from_fn(5, |index| index.to_string());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            SlimmerBox::retain(&mut boxed, |elem| match &**elem {
+                "3" => panic!("predicate panicked"),
+                elem => elem != "1",
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(&*boxed, &["0", "2", "3", "4"]);
+
+        let mut boxed: SlimmerBox<[String], u8> =
+            SlimmerBox::from_fn(5, |index| (index / 2).to_string());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            SlimmerBox::dedup_by(&mut boxed, |current, previous| {
+                assert_ne!(current, "2");
+                current == previous
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(&*boxed, &["0", "1", "2"]);
+    }
+
+    #[test]
+    fn str_in_place() {
+        let mut boxed: SlimmerBox<str, u8> = SlimmerBox::new("hello");
+        SlimmerBox::push_str(&mut boxed, ", world");
+        assert_eq!(&*boxed, "hello, world");
+        SlimmerBox::<str, _>::truncate(&mut boxed, 5);
+        assert_eq!(&*boxed, "hello");
+        assert!(SlimmerBox::try_push_str(&mut boxed, &"x".repeat(251)).is_err());
+        assert_eq!(&*boxed, "hello");
+
+        let mut boxed: SlimmerBox<str> = SlimmerBox::new("héllo");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            SlimmerBox::<str, _>::truncate(&mut boxed, 2)
+        }));
+        assert!(result.is_err());
+    }
+}
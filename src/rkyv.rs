@@ -2,7 +2,7 @@ use alloc::boxed::Box;
 use ptr_meta::Pointee;
 
 use crate::slim_pointee::SlimmerPointee;
-use crate::SlimmerBox;
+use crate::{PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox, ThinSlimmerBox};
 
 use rkyv::{boxed::ArchivedBox, Archive, ArchiveUnsized, Deserialize, Serialize, SerializeUnsized};
 
@@ -18,7 +18,7 @@ impl<T, SlimmerMetadata> Archive for SlimmerBox<T, SlimmerMetadata>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 
     Box<T>: Archive,
     T: ArchiveUnsized,
@@ -36,7 +36,7 @@ impl<S: rkyv::Fallible + ?Sized, T, SlimmerMetadata> Serialize<S> for SlimmerBox
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 
     Box<T>: Serialize<S>,
     T: SerializeUnsized<S>,
@@ -48,12 +48,14 @@ where
     }
 }
 
+/// Panics if the archived value is too large for SlimmerMetadata, just like [`SlimmerBox::from_box`].
+/// Use [`try_deserialize`] to get an error instead.
 impl<T, D, SlimmerMetadata> Deserialize<SlimmerBox<T, SlimmerMetadata>, D>
     for ArchivedBox<<T as ArchiveUnsized>::Archived>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 
     T: ArchiveUnsized,
     <T as ArchiveUnsized>::Archived: rkyv::DeserializeUnsized<T, D>,
@@ -68,9 +70,97 @@ where
     }
 }
 
+/// Deserializes an archived SlimmerBox, returning an error rather than panicking
+/// if the archived value is too large for SlimmerMetadata (for instance a [`Bounded`](crate::Bounded) one).
+///
+/// Works with any deserializer: the outer `Result` contains the deserializer's own error,
+/// the inner one whether the value fits. In the latter case, the deserialized box is handed back inside the error.
+///
+/// ```rust
+/// use slimmer_box::{Bounded, SlimmerBox};
+///
+/// let boxed: SlimmerBox<[i32]> = SlimmerBox::new(&[1, 2, 3, 4]);
+/// let bytes = rkyv::to_bytes::<_, 64>(&boxed).unwrap();
+/// let archived = unsafe { rkyv::archived_root::<SlimmerBox<[i32]>>(&bytes) };
+///
+/// let result = slimmer_box::rkyv::try_deserialize::<[i32], Bounded<u8, 3>, _>(archived, &mut rkyv::Infallible);
+/// assert_eq!(result.unwrap().unwrap_err().metadata(), 4);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn try_deserialize<T, SlimmerMetadata, D>(
+    archived: &ArchivedBox<<T as ArchiveUnsized>::Archived>,
+    deserializer: &mut D,
+) -> Result<
+    Result<
+        SlimmerBox<T, SlimmerMetadata>,
+        PointerMetadataDoesNotFitError<T, SlimmerMetadata, Box<T>>,
+    >,
+    D::Error,
+>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+
+    T: ArchiveUnsized,
+    <T as ArchiveUnsized>::Archived: rkyv::DeserializeUnsized<T, D>,
+    D: rkyv::Fallible + ?Sized,
+{
+    let boxed: Box<T> = archived.deserialize(deserializer)?;
+    Ok(SlimmerBox::try_from_box(boxed))
+}
+
+/// ThinSlimmerBox is archived into an [`ArchivedBox<T>`], just like a normal box.
+impl<T> Archive for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+
+    Box<T>: Archive,
+    T: ArchiveUnsized,
+{
+    type Archived = ArchivedBox<<T as ArchiveUnsized>::Archived>;
+    type Resolver = SlimmerBoxResolver<T>;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        rkyv::boxed::ArchivedBox::resolve_from_ref(self.as_ref(), pos, resolver.0, out)
+    }
+}
+
+impl<S: rkyv::Fallible + ?Sized, T> Serialize<S> for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+
+    Box<T>: Serialize<S>,
+    T: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let res = ArchivedBox::serialize_from_ref(self.as_ref(), serializer)?;
+        Ok(SlimmerBoxResolver(res))
+    }
+}
+
+impl<T, D> Deserialize<ThinSlimmerBox<T>, D> for ArchivedBox<<T as ArchiveUnsized>::Archived>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+
+    T: ArchiveUnsized,
+    <T as ArchiveUnsized>::Archived: rkyv::DeserializeUnsized<T, D>,
+    D: rkyv::Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<ThinSlimmerBox<T>, D::Error> {
+        let boxed: Box<T> = self.deserialize(deserializer)?;
+        Ok(ThinSlimmerBox::from_box(boxed))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::SlimmerBox;
+    use crate::{Bounded, SlimmerBox, ThinSlimmerBox};
 
     #[test]
     fn rkyv_roundtrip() {
@@ -84,4 +174,33 @@ mod tests {
             unsafe { rkyv::from_bytes_unchecked(&bytes) }.unwrap();
         assert_eq!(*boxed, *deserialized);
     }
+
+    #[test]
+    fn rkyv_bounded() {
+        let boxed: SlimmerBox<[i32], Bounded<u8, 3>> = SlimmerBox::new([1, 2, 3].as_slice());
+        let bytes = rkyv::to_bytes::<_, 64>(&boxed).unwrap();
+        let deserialized: SlimmerBox<[i32], Bounded<u8, 3>> =
+            unsafe { rkyv::from_bytes_unchecked(&bytes) }.unwrap();
+        assert_eq!(*boxed, *deserialized);
+
+        // An archived value which is too long is rejected rather than panicking
+        let boxed: SlimmerBox<[i32]> = SlimmerBox::new([1, 2, 3, 4].as_slice());
+        let bytes = rkyv::to_bytes::<_, 64>(&boxed).unwrap();
+        let archived = unsafe { rkyv::archived_root::<SlimmerBox<[i32]>>(&bytes) };
+        let error =
+            super::try_deserialize::<[i32], Bounded<u8, 3>, _>(archived, &mut rkyv::Infallible)
+                .unwrap()
+                .unwrap_err();
+        assert_eq!(error.metadata(), 4);
+        assert_eq!(&*error.into_input(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn rkyv_roundtrip_thin() {
+        let boxed: ThinSlimmerBox<[i32]> = ThinSlimmerBox::new([1, 2, 3, 4].as_slice());
+        let bytes = rkyv::to_bytes::<_, 64>(&boxed).unwrap();
+        let deserialized: ThinSlimmerBox<[i32]> =
+            unsafe { rkyv::from_bytes_unchecked(&bytes) }.unwrap();
+        assert_eq!(*boxed, *deserialized);
+    }
 }
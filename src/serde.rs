@@ -1,18 +1,20 @@
 
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
 
-use ::serde::de::{Deserialize, Deserializer};
+use ::serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
 use ::serde::ser::{Serialize, Serializer};
 use ptr_meta::Pointee;
 
-use crate::{SlimmerBox, SlimmerPointee};
+use crate::{SlimMetadata, SlimmerBox, SlimmerCow, SlimmerPointee, ThinSlimmerBox};
 
 impl<T: Serialize, SlimmerMetadata> Serialize for SlimmerBox<T, SlimmerMetadata>
 where
     T: ?Sized,
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -22,17 +24,17 @@ where
     }
 }
 
-impl<'de, T: Sized + Clone, SlimmerMetadata> Deserialize<'de> for SlimmerBox<T, SlimmerMetadata>
+impl<'de, T: Sized, SlimmerMetadata> Deserialize<'de> for SlimmerBox<T, SlimmerMetadata>
 where
     T: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata: TryFrom<<T as Pointee>::Metadata> + TryInto<<T as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
     T: Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        Deserialize::deserialize(deserializer).map(|val| SlimmerBox::new(&val))
+        Deserialize::deserialize(deserializer).map(SlimmerBox::from_value)
     }
 }
 
@@ -40,31 +42,174 @@ impl<'de, T, SlimmerMetadata> Deserialize<'de> for SlimmerBox<[T], SlimmerMetada
 where
     T: Sized,
     [T]: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata:
-        TryFrom<<[T] as Pointee>::Metadata> + TryInto<<[T] as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<[T] as Pointee>::Metadata>,
     T: Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        Deserialize::deserialize(deserializer)
-            .map(|val| SlimmerBox::from_box(Vec::into_boxed_slice(val)))
+        let val: Vec<T> = Deserialize::deserialize(deserializer)?;
+        SlimmerBox::try_from_box(val.into_boxed_slice()).map_err(D::Error::custom)
     }
 }
 
 impl<'de, SlimmerMetadata> Deserialize<'de> for SlimmerBox<str, SlimmerMetadata>
 where
     str: SlimmerPointee<SlimmerMetadata>,
-    SlimmerMetadata:
-        TryFrom<<str as Pointee>::Metadata> + TryInto<<str as Pointee>::Metadata> + Copy,
+    SlimmerMetadata: SlimMetadata<<str as Pointee>::Metadata>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let val: String = Deserialize::deserialize(deserializer)?;
+        SlimmerBox::try_from_box(val.into_boxed_str()).map_err(D::Error::custom)
+    }
+}
+
+impl<T: Serialize> Serialize for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ThinSlimmerBox<[T]>
+where
+    T: Sized,
+    T: Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         Deserialize::deserialize(deserializer)
-            .map(|val| SlimmerBox::from_box(String::into_boxed_str(val)))
+            .map(|val| ThinSlimmerBox::from_box(Vec::into_boxed_slice(val)))
+    }
+}
+
+impl<'de> Deserialize<'de> for ThinSlimmerBox<str> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+            .map(|val| ThinSlimmerBox::from_box(String::into_boxed_str(val)))
+    }
+}
+
+impl<'a, T: Serialize, SlimmerMetadata> Serialize for SlimmerCow<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes into a borrowed SlimmerCow if the format allows it, and into an owned one otherwise.
+impl<'de, SlimmerMetadata> Deserialize<'de> for SlimmerCow<'de, str, SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CowStrVisitor<SlimmerMetadata>(PhantomData<SlimmerMetadata>);
+
+        impl<'de, SlimmerMetadata> Visitor<'de> for CowStrVisitor<SlimmerMetadata>
+        where
+            str: SlimmerPointee<SlimmerMetadata>,
+            SlimmerMetadata: SlimMetadata<usize>,
+        {
+            type Value = SlimmerCow<'de, str, SlimmerMetadata>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                SlimmerCow::try_borrowed(v).map_err(E::custom)
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                let boxed = SlimmerBox::try_new(v).map_err(E::custom)?;
+                SlimmerCow::try_owned(boxed).map_err(E::custom)
+            }
+
+            fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+                let boxed = SlimmerBox::try_from_box(v.into_boxed_str()).map_err(E::custom)?;
+                SlimmerCow::try_owned(boxed).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(CowStrVisitor(PhantomData))
+    }
+}
+
+/// Deserializes into a borrowed SlimmerCow if the format allows it, and into an owned one otherwise.
+impl<'de, SlimmerMetadata> Deserialize<'de> for SlimmerCow<'de, [u8], SlimmerMetadata>
+where
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CowBytesVisitor<SlimmerMetadata>(PhantomData<SlimmerMetadata>);
+
+        impl<'de, SlimmerMetadata> Visitor<'de> for CowBytesVisitor<SlimmerMetadata>
+        where
+            [u8]: SlimmerPointee<SlimmerMetadata>,
+            SlimmerMetadata: SlimMetadata<usize>,
+        {
+            type Value = SlimmerCow<'de, [u8], SlimmerMetadata>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a byte array")
+            }
+
+            fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+                SlimmerCow::try_borrowed(v).map_err(E::custom)
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                let boxed = SlimmerBox::try_new(v).map_err(E::custom)?;
+                SlimmerCow::try_owned(boxed).map_err(E::custom)
+            }
+
+            fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                let boxed = SlimmerBox::try_from_box(v.into_boxed_slice()).map_err(E::custom)?;
+                SlimmerCow::try_owned(boxed).map_err(E::custom)
+            }
+
+            /// Formats without a bytes type (and our own Serialize impl) represent bytes as a sequence
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::new();
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                self.visit_byte_buf(bytes)
+            }
+        }
+
+        deserializer.deserialize_bytes(CowBytesVisitor(PhantomData))
     }
 }
 
@@ -79,6 +224,18 @@ mod tests {
         assert_tokens(&boxed, &[Token::U64(64)]);
     }
 
+    #[test]
+    fn deserialize_sized_without_clone() {
+        use ::serde::de::value::{Error, U64Deserializer};
+        use ::serde::de::IntoDeserializer;
+
+        // Mutex is not Clone, so the value has to be moved into the SlimmerBox
+        let deserializer: U64Deserializer<Error> = 64u64.into_deserializer();
+        let boxed: SlimmerBox<std::sync::Mutex<u64>, ()> =
+            Deserialize::deserialize(deserializer).unwrap();
+        assert_eq!(*boxed.lock().unwrap(), 64);
+    }
+
     #[test]
     fn serde_round_trip_slice() {
         let boxed: SlimmerBox<[i32]> = SlimmerBox::new([1, 2, 3, 4].as_slice());
@@ -100,4 +257,64 @@ mod tests {
         let boxed: SlimmerBox<str> = SlimmerBox::new("hello");
         assert_tokens(&boxed, &[Token::Str("hello")]);
     }
+
+    #[test]
+    fn deserialize_rejects_too_long() {
+        use crate::slim_metadata::Bounded;
+        use ::serde::de::value::{Error, StrDeserializer};
+
+        let result: Result<SlimmerBox<str, Bounded<u16, 4>>, Error> =
+            Deserialize::deserialize(StrDeserializer::<Error>::new("hello"));
+        assert!(result.is_err());
+        let result: Result<SlimmerBox<str, Bounded<u16, 5>>, Error> =
+            Deserialize::deserialize(StrDeserializer::<Error>::new("hello"));
+        assert_eq!(&*result.unwrap(), "hello");
+    }
+
+    #[test]
+    fn serde_round_trip_thin_str() {
+        let boxed: ThinSlimmerBox<str> = ThinSlimmerBox::new("hello");
+        assert_tokens(&boxed, &[Token::Str("hello")]);
+    }
+
+    #[test]
+    fn serde_cow_borrows_when_possible() {
+        let cow: SlimmerCow<str> = SlimmerCow::borrowed("hello");
+        assert_tokens(&cow, &[Token::BorrowedStr("hello")]);
+
+        use ::serde::de::value::{
+            BorrowedBytesDeserializer, BorrowedStrDeserializer, Error, StrDeserializer,
+        };
+        let input = "hello";
+        let cow: SlimmerCow<str> =
+            Deserialize::deserialize(BorrowedStrDeserializer::<Error>::new(input)).unwrap();
+        assert!(SlimmerCow::is_borrowed(&cow));
+        assert_eq!(SlimmerCow::to_ptr(&cow), input as *const str);
+
+        let cow: SlimmerCow<str> =
+            Deserialize::deserialize(StrDeserializer::<Error>::new(input)).unwrap();
+        assert!(SlimmerCow::is_owned(&cow));
+        assert_eq!(&*cow, "hello");
+
+        let cow: SlimmerCow<[u8]> =
+            Deserialize::deserialize(BorrowedBytesDeserializer::<Error>::new(b"abc")).unwrap();
+        assert!(SlimmerCow::is_borrowed(&cow));
+    }
+
+    #[test]
+    fn serde_round_trip_cow() {
+        let cow: SlimmerCow<str> = SlimmerCow::owned(SlimmerBox::new("hello"));
+        assert_tokens(&cow, &[Token::Str("hello")]);
+
+        let cow: SlimmerCow<[u8]> = SlimmerCow::owned(SlimmerBox::new([1, 2].as_slice()));
+        assert_tokens(
+            &cow,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U8(1),
+                Token::U8(2),
+                Token::SeqEnd,
+            ],
+        );
+    }
 }
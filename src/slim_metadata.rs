@@ -0,0 +1,393 @@
+/// A compact representation of pointer metadata of type `Full`.
+///
+/// This is what a SlimmerBox (and all other types in this crate) store in place of the full pointer metadata.
+///
+/// Implemented for:
+/// - `()`, as compact representation of the (lack of) metadata of sized types.
+/// - `u8`, `u16`, `u32`, `u64` and `usize`, as compact representation of the length of slices and strings.
+/// - [`U24`], [`U40`] and [`U48`], odd-width integers for even tighter packing of lengths.
+/// - [`Bounded<Int, LIMIT>`], to enforce a maximum length of your own choosing.
+///
+/// It can be implemented for your own types as well,
+/// after which they can be used as SlimmerMetadata of any [`SlimmerPointee`](crate::SlimmerPointee) that supports them.
+///
+/// # Safety
+///
+/// Whenever `try_compress(full)` returns `Some(slim)`, `slim.expand()` needs to return `full` again.
+///
+/// Furthermore, `MAX` needs to be exact: `try_compress(full)` needs to return `Some` for every `full <= MAX`
+/// and `None` for every `full > MAX`.
+///
+/// Unsafe code (like the Deref implementation of SlimmerBox) relies on this to rebuild valid pointers,
+/// which is why expanding is infallible and does not need to be checked.
+/// Likewise, code that has already checked a length against `MAX` (like [`SlimmerVec::set_len`](crate::SlimmerVec::set_len))
+/// relies on compressing it to succeed.
+pub unsafe trait SlimMetadata<Full>: Copy {
+    /// The largest full metadata value that can be stored.
+    const MAX: Full;
+
+    /// Converts full metadata into its compact representation, or returns `None` if it does not fit.
+    fn try_compress(full: Full) -> Option<Self>;
+
+    /// Converts the compact representation back into the full metadata.
+    fn expand(self) -> Full;
+}
+
+/// Sized types do not have any metadata.
+unsafe impl SlimMetadata<()> for () {
+    const MAX: () = ();
+
+    #[inline]
+    fn try_compress(_full: ()) -> Option<Self> {
+        Some(())
+    }
+
+    #[inline]
+    fn expand(self) {}
+}
+
+macro_rules! impl_slim_metadata_for_uint {
+    ($($int:ty),*) => {
+        $(
+            unsafe impl SlimMetadata<usize> for $int {
+                const MAX: usize = if (<$int>::MAX as u128) < (usize::MAX as u128) {
+                    <$int>::MAX as usize
+                } else {
+                    usize::MAX
+                };
+
+                #[inline]
+                fn try_compress(full: usize) -> Option<Self> {
+                    <$int>::try_from(full).ok()
+                }
+
+                #[inline]
+                fn expand(self) -> usize {
+                    // NOTE: Only truncates values larger than MAX, which try_compress never returns
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_slim_metadata_for_uint!(u8, u16, u32, u64, usize);
+
+/// The error returned when an integer is too large to fit in a [`U24`], [`U40`] or [`U48`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromIntError(());
+
+impl core::fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_str("out of range integral type conversion attempted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromIntError {}
+
+macro_rules! odd_width_uint {
+    ($(#[$attr:meta])* $name:ident, $bytes:literal) => {
+        $(#[$attr])*
+        ///
+        /// It is stored as a little-endian byte array, so it has an alignment of 1
+        /// and does not add any padding when used as SlimmerMetadata.
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name([u8; $bytes]);
+
+        impl $name {
+            /// The largest value that can be represented.
+            pub const MAX: $name = $name([0xFF; $bytes]);
+
+            /// The smallest value that can be represented.
+            pub const MIN: $name = $name([0; $bytes]);
+
+            /// The number of bits used to represent the value.
+            pub const BITS: u32 = $bytes * 8;
+        }
+
+        impl From<$name> for u64 {
+            fn from(value: $name) -> u64 {
+                let mut bytes = [0; 8];
+                bytes[..$bytes].copy_from_slice(&value.0);
+                u64::from_le_bytes(bytes)
+            }
+        }
+
+        impl TryFrom<u64> for $name {
+            type Error = TryFromIntError;
+
+            fn try_from(value: u64) -> Result<Self, Self::Error> {
+                if value >> $name::BITS != 0 {
+                    return Err(TryFromIntError(()));
+                }
+                let mut bytes = [0; $bytes];
+                bytes.copy_from_slice(&value.to_le_bytes()[..$bytes]);
+                Ok($name(bytes))
+            }
+        }
+
+        impl TryFrom<usize> for $name {
+            type Error = TryFromIntError;
+
+            fn try_from(value: usize) -> Result<Self, Self::Error> {
+                // NOTE: usize is never larger than u64 on supported targets
+                $name::try_from(value as u64)
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                u64::from(*self).cmp(&u64::from(*other))
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+                core::fmt::Debug::fmt(&u64::from(*self), f)
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+                core::fmt::Display::fmt(&u64::from(*self), f)
+            }
+        }
+
+        unsafe impl SlimMetadata<usize> for $name {
+            const MAX: usize = if ((1u128 << $name::BITS) - 1) < (usize::MAX as u128) {
+                ((1u64 << $name::BITS) - 1) as usize
+            } else {
+                usize::MAX
+            };
+
+            #[inline]
+            fn try_compress(full: usize) -> Option<Self> {
+                $name::try_from(full).ok()
+            }
+
+            #[inline]
+            fn expand(self) -> usize {
+                // NOTE: Only truncates values larger than MAX, which try_compress never returns
+                u64::from(self) as usize
+            }
+        }
+    };
+}
+
+odd_width_uint!(
+    /// A 24-bit unsigned integer, able to store lengths up to 16 MiB.
+    U24,
+    3
+);
+
+odd_width_uint!(
+    /// A 40-bit unsigned integer, able to store lengths up to 1 TiB.
+    U40,
+    5
+);
+
+odd_width_uint!(
+    /// A 48-bit unsigned integer, able to store lengths up to 256 TiB.
+    U48,
+    6
+);
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl From<U24> for usize {
+    fn from(value: U24) -> usize {
+        u64::from(value) as usize
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl From<U40> for usize {
+    fn from(value: U40) -> usize {
+        u64::from(value) as usize
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl From<U48> for usize {
+    fn from(value: U48) -> usize {
+        u64::from(value) as usize
+    }
+}
+
+/// Lengths of at most `LIMIT`, stored as `Int`.
+///
+/// This allows enforcing a maximum length (for instance one imposed by a protocol)
+/// whenever a SlimmerBox is constructed or deserialized,
+/// and then carrying that guarantee around in the type:
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use slimmer_box::SlimmerBox;
+/// use slimmer_box::slim_metadata::Bounded;
+///
+/// type Name = SlimmerBox<str, Bounded<u16, 1000>>;
+///
+/// let name: Name = SlimmerBox::new("Alice");
+/// assert_eq!(core::mem::size_of_val(&name), 10);
+/// assert!(Name::try_new(&"x".repeat(1001)).is_err());
+/// # }
+/// ```
+///
+/// If `LIMIT` is larger than what `Int` can store, the limit of `Int` applies instead.
+pub struct Bounded<Int, const LIMIT: usize>(Int);
+
+impl<Int: Copy, const LIMIT: usize> Clone for Bounded<Int, LIMIT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Int: Copy, const LIMIT: usize> Copy for Bounded<Int, LIMIT> {}
+
+impl<Int, const LIMIT: usize> core::fmt::Debug for Bounded<Int, LIMIT>
+where
+    Int: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        core::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+unsafe impl<Int, const LIMIT: usize> SlimMetadata<usize> for Bounded<Int, LIMIT>
+where
+    Int: SlimMetadata<usize>,
+{
+    const MAX: usize = if LIMIT < Int::MAX { LIMIT } else { Int::MAX };
+
+    #[inline]
+    fn try_compress(full: usize) -> Option<Self> {
+        if full <= LIMIT {
+            Int::try_compress(full).map(Bounded)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn expand(self) -> usize {
+        self.0.expand()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(<u8 as SlimMetadata<usize>>::MAX, 255);
+        assert_eq!(<u16 as SlimMetadata<usize>>::MAX, 65535);
+        assert_eq!(<usize as SlimMetadata<usize>>::MAX, usize::MAX);
+
+        assert_eq!(u8::try_compress(255).map(SlimMetadata::expand), Some(255));
+        assert_eq!(u8::try_compress(256), None);
+        assert_eq!(
+            u32::try_compress(70_000).map(SlimMetadata::expand),
+            Some(70_000)
+        );
+        assert_eq!(
+            u64::try_compress(usize::MAX).map(SlimMetadata::expand),
+            Some(usize::MAX)
+        );
+        assert_eq!(<() as SlimMetadata<()>>::try_compress(()), Some(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn odd_width_uints() {
+        use crate::SlimmerBox;
+
+        assert_eq!(u64::from(U24::MAX), 16_777_215);
+        assert_eq!(u64::from(U40::MAX), (1 << 40) - 1);
+        assert_eq!(u64::from(U48::MAX), (1 << 48) - 1);
+        assert_eq!(U24::try_from(16_777_216usize), Err(TryFromIntError(())));
+        assert_eq!(usize::from(U24::try_from(70_000usize).unwrap()), 70_000);
+        assert!(U24::try_from(256u64).unwrap() > U24::try_from(255u64).unwrap());
+
+        let text = "x".repeat(100_000);
+        let boxed: SlimmerBox<str, U24> = SlimmerBox::new(&*text);
+        assert_eq!(core::mem::size_of_val(&boxed), 11);
+        assert_eq!(&*boxed, &*text);
+
+        let boxed: SlimmerBox<[u8], U40> = SlimmerBox::new(&[1, 2, 3]);
+        assert_eq!(core::mem::size_of_val(&boxed), 13);
+        assert_eq!(&*boxed, &[1, 2, 3]);
+
+        let boxed: SlimmerBox<[u8], U48> = SlimmerBox::new(&[1, 2, 3]);
+        assert_eq!(core::mem::size_of_val(&boxed), 14);
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn bounded() {
+        use crate::SlimmerBox;
+
+        assert_eq!(<Bounded<u16, 1000> as SlimMetadata<usize>>::MAX, 1000);
+        assert_eq!(<Bounded<u8, 1000> as SlimMetadata<usize>>::MAX, 255);
+
+        let tags: SlimmerBox<[u32], Bounded<u8, 64>> = SlimmerBox::new(&[1; 64]);
+        assert_eq!(tags.len(), 64);
+        assert_eq!(core::mem::size_of_val(&tags), 9);
+        let error = SlimmerBox::<[u32], Bounded<u8, 64>>::try_new(&[1; 65]).unwrap_err();
+        assert_eq!(error.metadata(), 65);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn custom_slim_metadata() {
+        use crate::{SlimmerBox, SlimmerPointee};
+
+        /// Lengths of at most 15.
+        #[derive(Clone, Copy)]
+        struct Nibble(u8);
+
+        unsafe impl SlimMetadata<usize> for Nibble {
+            const MAX: usize = 15;
+
+            fn try_compress(full: usize) -> Option<Self> {
+                if full <= Self::MAX {
+                    Some(Nibble(full as u8))
+                } else {
+                    None
+                }
+            }
+
+            fn expand(self) -> usize {
+                self.0 as usize
+            }
+        }
+
+        unsafe impl SlimmerPointee<Nibble> for str {}
+
+        // Every length up to and including MAX compresses, and nothing above it does
+        for len in 0..=<Nibble as SlimMetadata<usize>>::MAX {
+            assert_eq!(Nibble::try_compress(len).map(SlimMetadata::expand), Some(len));
+        }
+        assert!(Nibble::try_compress(16).is_none());
+        assert!(Nibble::try_compress(usize::MAX).is_none());
+
+        let boxed: SlimmerBox<str, Nibble> = SlimmerBox::new("fifteen bytes!!");
+        assert_eq!(&*boxed, "fifteen bytes!!");
+        assert_eq!(core::mem::size_of_val(&boxed), 9);
+        assert!(SlimmerBox::<str, Nibble>::try_new("sixteen bytes!!!").is_err());
+
+        unsafe impl<T> SlimmerPointee<Nibble> for [T] {}
+
+        let boxed: SlimmerBox<[u8], Nibble> = SlimmerBox::from_array([7; 15]);
+        assert_eq!(boxed.len(), 15);
+        assert_eq!(SlimmerBox::metadata(&boxed), 15);
+        assert!(SlimmerBox::<[u8], Nibble>::try_new(&[7; 16]).is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use ptr_meta::Pointee;
 
+use crate::slim_metadata::{Bounded, U24, U40, U48};
+use crate::SlimMetadata;
+
 /// Trait which can be implemented by any pointer-like ('Pointee')types,
 /// as long as their metadata might be made smaller.
 ///
@@ -10,26 +13,18 @@ use ptr_meta::Pointee;
 ///
 /// It is _not_ implemented for trait objects, because their metadata is itself a pointer!
 ///
-/// # Safety
-///
-/// In a perfect world, we would have hade a `SlimmerMetadata: Into<Self as Pointee>::Metadata` bound on this trait.
-/// However, since pointer sizes differ on different compilation targets,
-/// current Rust never implements this conversion.
+/// Which compact representations are possible is described by [`SlimMetadata`];
+/// this trait marks which of those a particular pointee supports.
 ///
-/// As such, this trait functions under the assumption that
-/// ```ignore
-/// let slim_meta = meta.try_into();
-/// let recovered_meta = slim_meta.try_into().unwrap();
-/// ```
-/// will never fail.
+/// # Safety
 ///
-/// This assumption is very reasonable and in all likelyhood fulfilled by any pointer metadata type
-/// you ever throw at it.
-/// But since we cannot trust arbitrary safe code to do 'the right thing', this trait needs to be unsafe.
+/// Implementing `SlimmerPointee<SlimmerMetadata>` promises that a pointer to `Self` can be rebuilt
+/// from its address and the metadata returned by [`SlimMetadata::expand`].
+/// This holds for any pointee whose metadata is only its length, like slices and strings.
 pub unsafe trait SlimmerPointee<SlimmerMetadata>: Pointee
 where
     <Self as Pointee>::Metadata: Clone,
-    SlimmerMetadata: TryFrom<<Self as Pointee>::Metadata> + TryInto<<Self as Pointee>::Metadata>,
+    SlimmerMetadata: SlimMetadata<<Self as Pointee>::Metadata>,
 {
 }
 
@@ -95,3 +90,98 @@ unsafe impl SlimmerPointee<u64> for ::std::ffi::CStr {}
 
 #[cfg(feature = "std")]
 unsafe impl SlimmerPointee<u64> for ::std::ffi::OsStr {}
+
+/// Store at most 16777215 elements
+unsafe impl<T> SlimmerPointee<U24> for [T] {}
+
+/// Store at most 1099511627775 elements
+unsafe impl<T> SlimmerPointee<U40> for [T] {}
+
+/// Store at most 281474976710655 elements
+unsafe impl<T> SlimmerPointee<U48> for [T] {}
+
+/// Store at most 16777215 bytes == 16MiB
+unsafe impl SlimmerPointee<U24> for str {}
+
+/// Store at most 1099511627775 bytes == 1TiB
+unsafe impl SlimmerPointee<U40> for str {}
+
+/// Store at most 281474976710655 bytes == 256TiB
+unsafe impl SlimmerPointee<U48> for str {}
+
+#[cfg(feature = "std")]
+unsafe impl SlimmerPointee<U24> for ::std::ffi::CStr {}
+
+#[cfg(feature = "std")]
+unsafe impl SlimmerPointee<U40> for ::std::ffi::CStr {}
+
+#[cfg(feature = "std")]
+unsafe impl SlimmerPointee<U48> for ::std::ffi::CStr {}
+
+#[cfg(feature = "std")]
+unsafe impl SlimmerPointee<U24> for ::std::ffi::OsStr {}
+
+#[cfg(feature = "std")]
+unsafe impl SlimmerPointee<U40> for ::std::ffi::OsStr {}
+
+#[cfg(feature = "std")]
+unsafe impl SlimmerPointee<U48> for ::std::ffi::OsStr {}
+
+/// Store at most `LIMIT` elements
+unsafe impl<T, Int, const LIMIT: usize> SlimmerPointee<Bounded<Int, LIMIT>> for [T] where
+    Int: SlimMetadata<usize>
+{
+}
+
+/// Store at most `LIMIT` bytes
+unsafe impl<Int, const LIMIT: usize> SlimmerPointee<Bounded<Int, LIMIT>> for str where
+    Int: SlimMetadata<usize>
+{
+}
+
+#[cfg(feature = "std")]
+unsafe impl<Int, const LIMIT: usize> SlimmerPointee<Bounded<Int, LIMIT>> for ::std::ffi::CStr where
+    Int: SlimMetadata<usize>
+{
+}
+
+#[cfg(feature = "std")]
+unsafe impl<Int, const LIMIT: usize> SlimmerPointee<Bounded<Int, LIMIT>> for ::std::ffi::OsStr where
+    Int: SlimMetadata<usize>
+{
+}
+
+/// The SlimmerMetadata a pointee uses when none is specified, as in `SlimmerBox<T>`.
+///
+/// Implemented for:
+/// - Sized types, which use `()` as they do not have any metadata.
+/// - Slices, `str`, `CStr` and `OsStr`, which use `u32`.
+///
+/// To use a `SlimmerBox<T>` without naming its SlimmerMetadata for a pointee of your own,
+/// implement this trait next to [`SlimmerPointee`].
+pub trait DefaultSlimmerMetadata {
+    /// The default SlimmerMetadata.
+    type Metadata;
+}
+
+impl<T: Sized> DefaultSlimmerMetadata for T {
+    type Metadata = ();
+}
+
+impl<T> DefaultSlimmerMetadata for [T] {
+    type Metadata = u32;
+}
+
+impl DefaultSlimmerMetadata for str {
+    type Metadata = u32;
+}
+
+#[cfg(feature = "std")]
+impl DefaultSlimmerMetadata for ::std::ffi::CStr {
+    type Metadata = u32;
+}
+
+#[cfg(feature = "std")]
+impl DefaultSlimmerMetadata for ::std::ffi::OsStr {
+    type Metadata = u32;
+}
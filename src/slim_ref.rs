@@ -0,0 +1,599 @@
+//! Borrowed (non-owning) references whose 'fat' pointer is 'slimmer'.
+//!
+//! See [`SlimmerRef`] and [`SlimmerMut`] for more information.
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+use ptr_meta::Pointee;
+
+use crate::{DefaultSlimmerMetadata, PointerMetadataDoesNotFitError, SlimMetadata, SlimmerPointee};
+
+/// A shared reference `&'a T` whose 'fat' pointer is 'slimmer'.
+///
+/// This is to `&'a T` what [`SlimmerBox<T>`](crate::SlimmerBox) is to `Box<T>`:
+/// A `SlimmerRef<'a, str>` only takes up 12 bytes on 64-bit systems rather than the 16 bytes of a `&'a str`.
+/// Just like a normal reference, it is [`Copy`] and never null (so `Option<SlimmerRef<T>>` has the same size).
+///
+/// SlimmerRef never allocates.
+///
+/// ```rust
+/// use slimmer_box::SlimmerRef;
+///
+/// let text = String::from("hello, world!");
+/// let slim: SlimmerRef<str> = SlimmerRef::new(&text);
+/// assert_eq!(core::mem::size_of_val(&slim), 12);
+///
+/// let copy = slim;
+/// assert_eq!(&*slim, &*copy);
+/// assert_eq!(SlimmerRef::get(slim), "hello, world!");
+/// ```
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerRef<'a, T, SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    ptr: NonNull<()>,
+    meta: SlimmerMetadata,
+    marker: PhantomData<&'a T>,
+}
+
+/// A mutable reference `&'a mut T` whose 'fat' pointer is 'slimmer'.
+///
+/// This is the mutable counterpart of [`SlimmerRef`].
+///
+/// ```rust
+/// use slimmer_box::SlimmerMut;
+///
+/// let mut numbers = [3, 1, 2];
+/// let mut slim: SlimmerMut<[u64], u8> = SlimmerMut::new(&mut numbers);
+/// assert_eq!(core::mem::size_of_val(&slim), 9);
+/// slim.sort();
+/// assert_eq!(numbers, [1, 2, 3]);
+/// ```
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerMut<'a, T, SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    ptr: NonNull<()>,
+    meta: SlimmerMetadata,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T, SlimmerMetadata> SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Creates a new SlimmerRef from the given reference.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata.
+    pub fn new(value: &'a T) -> Self {
+        Self::try_new(value).unwrap()
+    }
+
+    /// Variant of `new` which will return an error if the value's metadata is too large instead of panicing.
+    pub fn try_new(
+        value: &'a T,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>> {
+        let (ptr, meta) = ptr_meta::PtrExt::to_raw_parts(value as *const T);
+        let meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
+        Ok(Self {
+            // SAFETY: References are never null
+            ptr: unsafe { NonNull::new_unchecked(ptr as *mut ()) },
+            meta,
+            marker: PhantomData,
+        })
+    }
+
+    /// Variant of `new` that skips its size check.
+    ///
+    /// # Safety
+    /// The caller must ensure that the value's metadata fits in SlimmerMetadata.
+    pub unsafe fn new_unchecked(value: &'a T) -> Self {
+        Self::try_new(value).unwrap_unchecked()
+    }
+
+    /// Turns the SlimmerRef back into a normal reference, with the full lifetime `'a`.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn get(this: Self) -> &'a T {
+        // SAFETY: Correct by construction
+        unsafe { &*SlimmerRef::to_ptr(&this) }
+    }
+
+    /// Obtains a raw read-only pointer to the referenced value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        ptr_meta::from_raw_parts(this.ptr.as_ptr(), SlimmerRef::metadata(this))
+    }
+
+    /// Retrieve access to the stored slimmer metadata value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn slim_metadata(this: &Self) -> SlimmerMetadata {
+        this.meta
+    }
+
+    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> <T as Pointee>::Metadata {
+        let aligned_len = SlimmerRef::slim_metadata(this);
+        aligned_len.expand()
+    }
+
+    /// Builds a SlimmerRef from its parts, which have already been checked.
+    ///
+    /// # Safety
+    /// `ptr` and `meta` need to form a valid reference to `T` for lifetime `'a`.
+    pub(crate) unsafe fn from_parts(ptr: NonNull<()>, meta: SlimmerMetadata) -> Self {
+        Self {
+            ptr,
+            meta,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Creates a new SlimmerMut from the given mutable reference.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata.
+    pub fn new(value: &'a mut T) -> Self {
+        Self::try_new(value).unwrap()
+    }
+
+    /// Variant of `new` which will return an error if the value's metadata is too large instead of panicing.
+    ///
+    /// On error, the mutable reference is handed back inside the error.
+    pub fn try_new(
+        value: &'a mut T,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata, &'a mut T>> {
+        let meta = ptr_meta::metadata(&*value);
+        let meta = match SlimmerMetadata::try_compress(meta) {
+            Some(meta) => meta,
+            None => return Err(PointerMetadataDoesNotFitError::new(meta, value)),
+        };
+        let ptr = value as *mut T as *mut ();
+        Ok(Self {
+            // SAFETY: References are never null
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            meta,
+            marker: PhantomData,
+        })
+    }
+
+    /// Variant of `new` that skips its size check.
+    ///
+    /// # Safety
+    /// The caller must ensure that the value's metadata fits in SlimmerMetadata.
+    pub unsafe fn new_unchecked(value: &'a mut T) -> Self {
+        Self::try_new(value).unwrap_unchecked()
+    }
+
+    /// Turns the SlimmerMut back into a normal mutable reference, with the full lifetime `'a`.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_mut(this: Self) -> &'a mut T {
+        let ptr = ptr_meta::from_raw_parts_mut(this.ptr.as_ptr(), SlimmerMut::metadata(&this));
+        // SAFETY: Correct by construction
+        unsafe { &mut *ptr }
+    }
+
+    /// Turns the SlimmerMut into a shared SlimmerRef, with the full lifetime `'a`.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_slim_ref(this: Self) -> SlimmerRef<'a, T, SlimmerMetadata> {
+        // SAFETY: A mutable reference can always be turned into a shared one
+        unsafe { SlimmerRef::from_parts(this.ptr, this.meta) }
+    }
+
+    /// Borrows the SlimmerMut as a shared SlimmerRef for a shorter lifetime.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn as_slim_ref(this: &Self) -> SlimmerRef<'_, T, SlimmerMetadata> {
+        // SAFETY: We are borrowed immutably for the lifetime of the result
+        unsafe { SlimmerRef::from_parts(this.ptr, this.meta) }
+    }
+
+    /// Reborrows the SlimmerMut for a shorter lifetime, so it can be passed on without giving it up.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn reborrow(this: &mut Self) -> SlimmerMut<'_, T, SlimmerMetadata> {
+        // SAFETY: We are borrowed mutably for the lifetime of the result
+        unsafe { SlimmerMut::from_parts(this.ptr, this.meta) }
+    }
+
+    /// Retrieve access to the stored slimmer metadata value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn slim_metadata(this: &Self) -> SlimmerMetadata {
+        this.meta
+    }
+
+    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> <T as Pointee>::Metadata {
+        let aligned_len = SlimmerMut::slim_metadata(this);
+        aligned_len.expand()
+    }
+
+    /// Builds a SlimmerMut from its parts, which have already been checked.
+    ///
+    /// # Safety
+    /// `ptr` and `meta` need to form a valid, unique, mutable reference to `T` for lifetime `'a`.
+    pub(crate) unsafe fn from_parts(ptr: NonNull<()>, meta: SlimmerMetadata) -> Self {
+        Self {
+            ptr,
+            meta,
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, T: Sync, SlimmerMetadata: Send> Send for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<'a, T: Sync, SlimmerMetadata: Sync> Sync for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<'a, T: Send, SlimmerMetadata: Send> Send for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<'a, T: Sync, SlimmerMetadata: Sync> Sync for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<'a, T, SlimmerMetadata> Clone for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> Copy for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<'a, T, SlimmerMetadata> From<SlimmerMut<'a, T, SlimmerMetadata>>
+    for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn from(slim_mut: SlimmerMut<'a, T, SlimmerMetadata>) -> Self {
+        SlimmerMut::into_slim_ref(slim_mut)
+    }
+}
+
+// NOTE: `TryFrom<&'a T>` cannot be implemented generically, as it would overlap with
+// core's blanket `impl<T, U: Into<T>> TryFrom<U> for T` for pointees defined in other crates.
+// Hence it is implemented for the (unsized) pointees supported by this crate instead.
+macro_rules! impl_try_from_ref {
+    ($({$($generics:tt)*} $pointee:ty),* $(,)?) => {
+        $(
+            impl<'a, $($generics)* SlimmerMetadata> TryFrom<&'a $pointee>
+                for SlimmerRef<'a, $pointee, SlimmerMetadata>
+            where
+                $pointee: SlimmerPointee<SlimmerMetadata>,
+                SlimmerMetadata: SlimMetadata<<$pointee as Pointee>::Metadata>,
+            {
+                type Error = PointerMetadataDoesNotFitError<$pointee, SlimmerMetadata>;
+
+                fn try_from(value: &'a $pointee) -> Result<Self, Self::Error> {
+                    SlimmerRef::try_new(value)
+                }
+            }
+
+            impl<'a, $($generics)* SlimmerMetadata> TryFrom<&'a mut $pointee>
+                for SlimmerMut<'a, $pointee, SlimmerMetadata>
+            where
+                $pointee: SlimmerPointee<SlimmerMetadata>,
+                SlimmerMetadata: SlimMetadata<<$pointee as Pointee>::Metadata>,
+            {
+                type Error =
+                    PointerMetadataDoesNotFitError<$pointee, SlimmerMetadata, &'a mut $pointee>;
+
+                fn try_from(value: &'a mut $pointee) -> Result<Self, Self::Error> {
+                    SlimmerMut::try_new(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_ref!({T,} [T], {} str);
+
+#[cfg(feature = "std")]
+impl_try_from_ref!({} ::std::ffi::CStr, {} ::std::ffi::OsStr);
+
+impl<'a, T, SlimmerMetadata> Deref for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        SlimmerRef::get(*self)
+    }
+}
+
+impl<'a, T, SlimmerMetadata> Deref for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        let ptr = ptr_meta::from_raw_parts(self.ptr.as_ptr(), SlimmerMut::metadata(self));
+        // SAFETY: Correct by construction
+        unsafe { &*ptr }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> DerefMut for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let ptr = ptr_meta::from_raw_parts_mut(self.ptr.as_ptr(), SlimmerMut::metadata(self));
+        // SAFETY: Correct by construction
+        unsafe { &mut *ptr }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::borrow::Borrow<T> for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> AsRef<T> for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::borrow::Borrow<T> for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::borrow::BorrowMut<T> for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> AsRef<T> for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> AsMut<T> for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::fmt::Debug for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, T, SlimmerMetadata> core::fmt::Debug for SlimmerMut<'a, T, SlimmerMetadata>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, T: PartialEq, SlimmerMetadata> PartialEq for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<'a, T: Eq, SlimmerMetadata> Eq for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<'a, T: PartialOrd, SlimmerMetadata> PartialOrd for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: Ord, SlimmerMetadata> Ord for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T: core::hash::Hash, SlimmerMetadata> core::hash::Hash
+    for SlimmerRef<'a, T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::SlimmerBox;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<SlimmerRef<str>>(), 12);
+        assert_eq!(core::mem::size_of::<Option<SlimmerRef<str>>>(), 12);
+        assert_eq!(core::mem::size_of::<SlimmerMut<[u64], u16>>(), 10);
+        assert_eq!(core::mem::size_of::<SlimmerRef<u64, ()>>(), 8);
+    }
+
+    #[test]
+    fn too_long() {
+        let long = [0u8; 256];
+        assert!(SlimmerRef::<[u8], u8>::try_new(&long[..]).is_err());
+        assert!(SlimmerRef::<[u8], u8>::try_new(&long[..255]).is_ok());
+    }
+
+    #[test]
+    fn try_from() {
+        let long = [0u8; 256];
+        let slim = SlimmerRef::<[u8], u8>::try_from(&long[..255]).unwrap();
+        assert_eq!(slim.len(), 255);
+        let error = SlimmerRef::<[u8], u8>::try_from(&long[..]).unwrap_err();
+        assert_eq!(error.metadata(), 256);
+
+        let mut numbers = [0u8; 256];
+        let slim: SlimmerMut<[u8], u8> = (&mut numbers[..3]).try_into().unwrap();
+        assert_eq!(slim.len(), 3);
+        let error = SlimmerMut::<[u8], u8>::try_from(&mut numbers[..]).unwrap_err();
+        assert_eq!(error.metadata(), 256);
+        // The mutable reference is handed back on error
+        error.into_input()[0] = 42;
+        assert_eq!(numbers[0], 42);
+    }
+
+    #[test]
+    fn mutation_through_reborrow() {
+        let mut text = std::string::String::from("hello");
+        let mut slim: SlimmerMut<str> = SlimmerMut::new(text.as_mut_str());
+        SlimmerMut::reborrow(&mut slim).make_ascii_uppercase();
+        assert_eq!(&*SlimmerMut::as_slim_ref(&slim), "HELLO");
+        let shared: SlimmerRef<str> = slim.into();
+        assert_eq!(&*shared, "HELLO");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn borrowed_from_slimmer_box() {
+        let mut boxed: SlimmerBox<[u64], u8> = SlimmerBox::new(&[3, 2, 1][..]);
+        SlimmerBox::as_slim_mut(&mut boxed).sort();
+        let slim = SlimmerBox::as_slim_ref(&boxed);
+        assert_eq!(core::mem::size_of_val(&slim), 9);
+        assert_eq!(&*slim, &[1, 2, 3]);
+    }
+}
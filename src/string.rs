@@ -0,0 +1,573 @@
+//! A growable UTF-8 string whose length and capacity are 'slimmer'.
+//!
+//! See [`SlimmerString`] for more information.
+use allocator_api2::alloc::Global;
+use core::{
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use crate::{PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox, SlimmerPointee, SlimmerVec};
+
+/// A growable alternative to [`String`](alloc::string::String) which stores its length and capacity in a (smaller) `SlimmerMetadata`.
+///
+/// This is to `String` what [`SlimmerVec<u8>`] is to `Vec<u8>`:
+/// A `SlimmerString<u32>` only takes up 16 bytes on 64-bit systems (rather than 24),
+/// and it will never grow beyond the maximum number of bytes that fits in `SlimmerMetadata`.
+///
+/// Once you are done building, [`SlimmerString::into_slimmer_box`] turns the string into a [`SlimmerBox<str, SlimmerMetadata>`]
+/// without copying its contents.
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use slimmer_box::{SlimmerBox, SlimmerString};
+///
+/// let mut string: SlimmerString = SlimmerString::new();
+/// assert_eq!(core::mem::size_of_val(&string), 16);
+/// string.push_str("hello");
+/// string.push(',');
+/// write!(string, " {}!", "world").unwrap();
+///
+/// let boxed: SlimmerBox<str> = string.into_slimmer_box();
+/// assert_eq!(&*boxed, "hello, world!");
+/// ```
+pub struct SlimmerString<SlimmerMetadata = u32>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    vec: SlimmerVec<u8, SlimmerMetadata>,
+}
+
+impl<SlimmerMetadata> SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Creates a new, empty SlimmerString.
+    ///
+    /// This does not allocate.
+    pub fn new() -> Self {
+        Self {
+            vec: SlimmerVec::new(),
+        }
+    }
+
+    /// Creates a new, empty SlimmerString with room for at least `capacity` bytes.
+    ///
+    /// Panics if `capacity` does not fit in SlimmerMetadata.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::try_with_capacity(capacity).unwrap()
+    }
+
+    /// Variant of `with_capacity` which will return an error if `capacity` does not fit instead of panicing.
+    pub fn try_with_capacity(
+        capacity: usize,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<str, SlimmerMetadata>> {
+        let vec = SlimmerVec::try_with_capacity(capacity)
+            .map_err(|error| PointerMetadataDoesNotFitError::new(error.metadata(), ()))?;
+        Ok(Self { vec })
+    }
+
+    /// Converts a vector of bytes into a SlimmerString, if it contains valid UTF-8.
+    ///
+    /// Otherwise, the vector is returned unchanged.
+    pub fn from_utf8(
+        vec: SlimmerVec<u8, SlimmerMetadata>,
+    ) -> Result<Self, SlimmerVec<u8, SlimmerMetadata>> {
+        match core::str::from_utf8(&vec) {
+            Ok(_) => Ok(Self { vec }),
+            Err(_) => Err(vec),
+        }
+    }
+
+    /// Converts a vector of bytes into a SlimmerString without checking whether it contains valid UTF-8.
+    ///
+    /// # Safety
+    /// The bytes must be valid UTF-8.
+    pub unsafe fn from_utf8_unchecked(vec: SlimmerVec<u8, SlimmerMetadata>) -> Self {
+        Self { vec }
+    }
+
+    /// Turns a SlimmerBox into a SlimmerString.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    pub fn from_slimmer_box(boxed: SlimmerBox<str, SlimmerMetadata>) -> Self {
+        let meta = SlimmerBox::slim_metadata(&boxed);
+        let ptr = SlimmerBox::into_raw(boxed) as *mut u8;
+        let bytes = SlimmerBox {
+            // SAFETY: SlimmerBox ensures the pointer is non-null
+            ptr: unsafe { NonNull::new_unchecked(ptr as *mut ()) },
+            meta,
+            alloc: ManuallyDrop::new(Global),
+            marker: PhantomData,
+        };
+        Self {
+            vec: SlimmerVec::from_slimmer_box(bytes),
+        }
+    }
+
+    /// Turns the SlimmerString into a SlimmerBox.
+    ///
+    /// Any excess capacity is released first (using `realloc`, which can usually be done in place).
+    pub fn into_slimmer_box(self) -> SlimmerBox<str, SlimmerMetadata> {
+        let bytes = self.vec.into_slimmer_box();
+        let meta = SlimmerBox::slim_metadata(&bytes);
+        let ptr = SlimmerBox::into_raw(bytes) as *mut u8;
+        SlimmerBox {
+            // SAFETY: SlimmerBox ensures the pointer is non-null
+            ptr: unsafe { NonNull::new_unchecked(ptr as *mut ()) },
+            meta,
+            alloc: ManuallyDrop::new(Global),
+            marker: PhantomData,
+        }
+    }
+
+    /// Turns the SlimmerString into its underlying bytes.
+    pub fn into_bytes(self) -> SlimmerVec<u8, SlimmerMetadata> {
+        self.vec
+    }
+
+    /// Extracts a string slice containing the entire string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: The bytes are always valid UTF-8
+        unsafe { core::str::from_utf8_unchecked(&self.vec) }
+    }
+
+    /// Extracts a mutable string slice containing the entire string.
+    pub fn as_mut_str(&mut self) -> &mut str {
+        // SAFETY: The bytes are always valid UTF-8
+        unsafe { core::str::from_utf8_unchecked_mut(&mut self.vec) }
+    }
+
+    /// The length of the string, in bytes.
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns true if the string has a length of zero.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// The number of bytes the string can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    ///
+    /// Panics if the new capacity does not fit in SlimmerMetadata.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional).unwrap()
+    }
+
+    /// Variant of `reserve` which will return an error if the new capacity does not fit instead of panicing.
+    pub fn try_reserve(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), PointerMetadataDoesNotFitError<str, SlimmerMetadata>> {
+        self.vec
+            .try_reserve(additional)
+            .map_err(|error| PointerMetadataDoesNotFitError::new(error.metadata(), ()))
+    }
+
+    /// Shrinks the capacity of the string to its length.
+    pub fn shrink_to_fit(&mut self) {
+        self.vec.shrink_to_fit()
+    }
+
+    /// Appends the given string slice to the end of this string.
+    ///
+    /// Panics if the new length does not fit in SlimmerMetadata.
+    pub fn push_str(&mut self, string: &str) {
+        self.try_push_str(string).unwrap()
+    }
+
+    /// Variant of `push_str` which will return an error if the new length does not fit instead of panicing.
+    ///
+    /// If an error is returned, the string is left unchanged.
+    pub fn try_push_str(
+        &mut self,
+        string: &str,
+    ) -> Result<(), PointerMetadataDoesNotFitError<str, SlimmerMetadata>> {
+        self.try_insert_str(self.len(), string)
+    }
+
+    /// Appends the given character to the end of this string.
+    ///
+    /// Panics if the new length does not fit in SlimmerMetadata.
+    pub fn push(&mut self, ch: char) {
+        self.try_push(ch).unwrap()
+    }
+
+    /// Variant of `push` which will return an error if the new length does not fit instead of panicing.
+    ///
+    /// If an error is returned, the string is left unchanged.
+    pub fn try_push(
+        &mut self,
+        ch: char,
+    ) -> Result<(), PointerMetadataDoesNotFitError<str, SlimmerMetadata>> {
+        self.try_push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Removes the last character from the string and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        self.vec.truncate(self.len() - ch.len_utf8());
+        Some(ch)
+    }
+
+    /// Inserts a character at byte position `index`.
+    ///
+    /// Panics if `index` is not on a character boundary, or if the new length does not fit in SlimmerMetadata.
+    pub fn insert(&mut self, index: usize, ch: char) {
+        self.insert_str(index, ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Inserts a string slice at byte position `index`.
+    ///
+    /// Panics if `index` is not on a character boundary, or if the new length does not fit in SlimmerMetadata.
+    pub fn insert_str(&mut self, index: usize, string: &str) {
+        self.try_insert_str(index, string).unwrap()
+    }
+
+    /// Variant of `insert_str` which will return an error if the new length does not fit instead of panicing.
+    ///
+    /// If an error is returned, the string is left unchanged.
+    ///
+    /// Still panics if `index` is not on a character boundary.
+    pub fn try_insert_str(
+        &mut self,
+        index: usize,
+        string: &str,
+    ) -> Result<(), PointerMetadataDoesNotFitError<str, SlimmerMetadata>> {
+        assert!(
+            self.is_char_boundary(index),
+            "index is not on a char boundary"
+        );
+        self.try_reserve(string.len())?;
+        let len = self.len();
+        // SAFETY: We just reserved room for the new bytes,
+        // and inserting valid UTF-8 at a char boundary results in valid UTF-8.
+        unsafe {
+            let ptr = self.vec.as_mut_ptr().add(index);
+            core::ptr::copy(ptr, ptr.add(string.len()), len - index);
+            core::ptr::copy_nonoverlapping(string.as_ptr(), ptr, string.len());
+            self.vec.set_len(len + string.len());
+        }
+        Ok(())
+    }
+
+    /// Removes and returns the character at byte position `index`.
+    ///
+    /// Panics if `index` is not on a character boundary or out of bounds.
+    pub fn remove(&mut self, index: usize) -> char {
+        let ch = self[index..]
+            .chars()
+            .next()
+            .expect("cannot remove a char from the end of a string");
+        self.vec.drain(index..index + ch.len_utf8());
+        ch
+    }
+
+    /// Shortens the string to `new_len` bytes.
+    ///
+    /// Does nothing if `new_len` is greater than the current length.
+    ///
+    /// Panics if `new_len` is not on a character boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(
+                self.is_char_boundary(new_len),
+                "new_len is not on a char boundary"
+            );
+            self.vec.truncate(new_len)
+        }
+    }
+
+    /// Removes all contents from the string, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+}
+
+impl<SlimmerMetadata> Default for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<SlimmerMetadata> Deref for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<SlimmerMetadata> DerefMut for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<SlimmerMetadata> AsRef<str> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<SlimmerMetadata> AsRef<[u8]> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<SlimmerMetadata> core::borrow::Borrow<str> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<SlimmerMetadata> core::fmt::Write for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Appends the string slice, returning an error if the new length does not fit in SlimmerMetadata.
+    fn write_str(&mut self, string: &str) -> core::fmt::Result {
+        self.try_push_str(string).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl<SlimmerMetadata> Extend<char> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        for ch in iter {
+            self.push(ch);
+        }
+    }
+}
+
+impl<'a, SlimmerMetadata> Extend<&'a str> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for string in iter {
+            self.push_str(string);
+        }
+    }
+}
+
+impl<SlimmerMetadata> core::iter::FromIterator<char> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut string = Self::new();
+        string.extend(iter);
+        string
+    }
+}
+
+impl<SlimmerMetadata> Clone for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec.clone(),
+        }
+    }
+}
+
+impl<SlimmerMetadata> core::fmt::Debug for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<SlimmerMetadata> core::fmt::Display for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<SlimmerMetadata> PartialEq for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<SlimmerMetadata> PartialEq<str> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        PartialEq::eq(&**self, other)
+    }
+}
+
+impl<'a, SlimmerMetadata> PartialEq<&'a str> for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        PartialEq::eq(&**self, *other)
+    }
+}
+
+impl<SlimmerMetadata> Eq for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+}
+
+impl<SlimmerMetadata> PartialOrd for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<SlimmerMetadata> Ord for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<SlimmerMetadata> core::hash::Hash for SlimmerString<SlimmerMetadata>
+where
+    str: SlimmerPointee<SlimmerMetadata>,
+    [u8]: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<SlimmerString>(), 16);
+        assert_eq!(core::mem::size_of::<Option<SlimmerString<u16>>>(), 12);
+    }
+
+    #[test]
+    fn editing() {
+        let mut string: SlimmerString = SlimmerString::new();
+        string.push_str("hllo");
+        string.insert(1, 'e');
+        string.push('🦀');
+        assert_eq!(string, "hello🦀");
+        assert_eq!(string.pop(), Some('🦀'));
+        assert_eq!(string.remove(0), 'h');
+        string.truncate(2);
+        assert_eq!(string, "el");
+    }
+
+    #[test]
+    fn overflowing_metadata() {
+        let mut string: SlimmerString<u8> = SlimmerString::new();
+        string.push_str(&"a".repeat(250));
+        assert!(string.try_push_str("123456").is_err());
+        assert!(write!(string, "{}", 123456).is_err());
+        string.push_str("12345");
+        assert_eq!(string.len(), 255);
+        assert_eq!(string.into_slimmer_box().len(), 255);
+    }
+
+    #[test]
+    fn slimmer_box_roundtrip() {
+        let boxed: SlimmerBox<str, u16> = SlimmerBox::new("hello");
+        let mut string = SlimmerString::from_slimmer_box(boxed);
+        string.push_str(", world");
+        assert_eq!(&*string.into_slimmer_box(), "hello, world");
+    }
+}
@@ -0,0 +1,748 @@
+//! Thread-safe reference-counted pointers whose 'fat' pointer is 'slimmer'.
+//!
+//! See [`SlimmerArc<T>`] for more information.
+use alloc::boxed::Box;
+use allocator_api2::alloc::Global;
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::Deref,
+    ptr::NonNull,
+    sync::atomic::{self, AtomicUsize, Ordering},
+};
+use ptr_meta::Pointee;
+
+use crate::{
+    header, AllocError, CloneUnsized, DefaultSlimmerMetadata, PointerMetadataDoesNotFitError,
+    SlimMetadata, SlimmerBox,
+    SlimmerPointee,
+};
+
+/// The reference counts, stored in the same allocation right in front of the value.
+struct ArcHeader {
+    strong: AtomicUsize,
+    /// Number of weak pointers + 1 (an implicit weak pointer held by all strong pointers together).
+    weak: AtomicUsize,
+}
+
+/// A thread-safe reference-counted alternative to [`Arc<T>`](alloc::sync::Arc) whose 'fat' pointer is 'slimmer'.
+///
+/// Just like [`SlimmerBox<T, SlimmerMetadata>`], the metadata of the pointer is stored in
+/// a (smaller) `SlimmerMetadata` right next to the pointer.
+/// The reference counts are stored in the heap allocation, right in front of the value.
+///
+/// As such, a `SlimmerArc<str>` only takes up 12 bytes on 64-bit systems
+/// (rather than the 16 bytes of a normal `Arc<str>`),
+/// and `Option<SlimmerArc<T>>` is just as large as `SlimmerArc<T>`.
+///
+/// Most operations are associated functions rather than methods, to not interfere with [`Deref`].
+///
+/// ```rust
+/// use slimmer_box::SlimmerArc;
+///
+/// let shared: SlimmerArc<str> = SlimmerArc::new("hello, world!");
+/// let other = shared.clone();
+/// assert_eq!(core::mem::size_of_val(&shared), 12);
+/// assert_eq!(SlimmerArc::strong_count(&shared), 2);
+/// assert!(SlimmerArc::ptr_eq(&shared, &other));
+/// assert_eq!(&*other, "hello, world!");
+/// ```
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerArc<T, SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    ptr: NonNull<()>,
+    meta: SlimmerMetadata,
+    marker: PhantomData<T>,
+}
+
+/// A non-owning version of [`SlimmerArc`], which does not keep the value alive.
+///
+/// Obtained using [`SlimmerArc::downgrade`]. Turn it back into a [`SlimmerArc`] using [`Weak::upgrade`].
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct Weak<T, SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    ptr: NonNull<()>,
+    meta: SlimmerMetadata,
+    marker: PhantomData<T>,
+}
+
+impl<T, SlimmerMetadata> SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Creates a new SlimmerArc from the given value (which may be a slice, string or other dynamically sized type).
+    ///
+    /// This involves cloning the value, and as such only works for types whose contents are cloneable.
+    /// Otherwise, use `from_box`.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata.
+    pub fn new(value: &T) -> Self
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new(value).unwrap()
+    }
+
+    /// Variant of `new` which will return an error if the value's metadata is too large instead of panicing.
+    pub fn try_new(value: &T) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata>>
+    where
+        T: CloneUnsized,
+    {
+        Self::try_new_fallible(value).map_err(AllocError::or_handle_alloc_error)
+    }
+
+    /// Variant of `try_new` which will also return an error if allocating memory fails,
+    /// rather than calling [`handle_alloc_error`](alloc::alloc::handle_alloc_error).
+    pub fn try_new_fallible(value: &T) -> Result<Self, AllocError<T, SlimmerMetadata>>
+    where
+        T: CloneUnsized,
+    {
+        let meta = ptr_meta::metadata(value);
+        let slim_meta = SlimmerMetadata::try_compress(meta)
+            .ok_or_else(|| PointerMetadataDoesNotFitError::new(meta, ()))?;
+
+        // SAFETY: ArcHeader is not zero-sized
+        let ptr = unsafe { header::try_alloc_clone::<ArcHeader, T>(value) }
+            .map_err(AllocError::AllocationFailed)?;
+        // SAFETY: The header was allocated but not yet initialized
+        unsafe { header::header::<ArcHeader>(ptr.as_ptr()).write(ArcHeader::new()) };
+        Ok(Self {
+            ptr,
+            meta: slim_meta,
+            marker: PhantomData,
+        })
+    }
+
+    /// Turns a Box into a SlimmerArc.
+    ///
+    /// This moves the value into a new allocation which also contains the reference counts.
+    ///
+    /// Panics if the pointer's metadata is too large to made slimmer.
+    pub fn from_box(boxed: Box<T>) -> Self {
+        Self::try_from_box(boxed).unwrap()
+    }
+
+    /// Variant of `from_box` which will return an error if the value's metadata is too large instead of panicing.
+    ///
+    /// On error, the box is handed back inside the error.
+    pub fn try_from_box(
+        boxed: Box<T>,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<T, SlimmerMetadata, Box<T>>> {
+        let meta = ptr_meta::metadata(&*boxed);
+        let slim_meta = match SlimmerMetadata::try_compress(meta) {
+            Some(slim_meta) => slim_meta,
+            None => return Err(PointerMetadataDoesNotFitError::new(meta, boxed)),
+        };
+
+        let value_layout = Layout::for_value(&*boxed);
+        let box_ptr = Box::into_raw(boxed);
+        unsafe {
+            // SAFETY: ArcHeader is not zero-sized
+            let ptr = header::alloc::<ArcHeader>(value_layout);
+            header::header::<ArcHeader>(ptr.as_ptr()).write(ArcHeader::new());
+            // SAFETY: The value is moved (rather than cloned) into the new allocation,
+            // after which the old allocation is freed without dropping the value.
+            core::ptr::copy_nonoverlapping(
+                box_ptr as *const u8,
+                ptr.as_ptr() as *mut u8,
+                value_layout.size(),
+            );
+            if value_layout.size() != 0 {
+                alloc::alloc::dealloc(box_ptr as *mut u8, value_layout);
+            }
+            Ok(Self {
+                ptr,
+                meta: slim_meta,
+                marker: PhantomData,
+            })
+        }
+    }
+
+    /// Returns the contained value as a [`SlimmerBox`], if this is the only strong pointer to it.
+    ///
+    /// Otherwise, the SlimmerArc is returned unchanged as error.
+    /// Any [`Weak`] pointers that remain will no longer be upgradeable.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn try_unwrap(this: Self) -> Result<SlimmerBox<T, SlimmerMetadata>, Self> {
+        if this
+            .header()
+            .strong
+            .compare_exchange(1, 0, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(this);
+        }
+        atomic::fence(Ordering::Acquire);
+        let value_layout = Layout::for_value(&*this);
+        let box_ptr = if value_layout.size() == 0 {
+            value_layout.align() as *mut u8
+        } else {
+            let box_ptr = unsafe { alloc::alloc::alloc(value_layout) };
+            if box_ptr.is_null() {
+                alloc::alloc::handle_alloc_error(value_layout)
+            }
+            box_ptr
+        };
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                this.ptr.as_ptr() as *const u8,
+                box_ptr,
+                value_layout.size(),
+            );
+        }
+        let meta = this.meta;
+        // Drops the implicit weak pointer, freeing the allocation (without dropping the moved value)
+        // unless other Weak pointers still exist.
+        let _weak = Weak::<T, SlimmerMetadata> {
+            ptr: this.ptr,
+            meta,
+            marker: PhantomData,
+        };
+        core::mem::forget(this);
+
+        Ok(SlimmerBox {
+            // SAFETY: Either a fresh allocation or dangling, never null
+            ptr: unsafe { NonNull::new_unchecked(box_ptr as *mut ()) },
+            meta,
+            alloc: ManuallyDrop::new(Global),
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates a new [`Weak`] pointer to this value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn downgrade(this: &Self) -> Weak<T, SlimmerMetadata> {
+        let weak = &this.header().weak;
+        let mut current = weak.load(Ordering::Relaxed);
+        loop {
+            // usize::MAX means the weak count is temporarily 'locked' by `get_mut`.
+            if current == usize::MAX {
+                core::hint::spin_loop();
+                current = weak.load(Ordering::Relaxed);
+                continue;
+            }
+            if current >= header::MAX_REFCOUNT {
+                header::abort();
+            }
+            match weak.compare_exchange_weak(
+                current,
+                current + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(old) => current = old,
+            }
+        }
+        Weak {
+            ptr: this.ptr,
+            meta: this.meta,
+            marker: PhantomData,
+        }
+    }
+
+    /// The number of strong (SlimmerArc) pointers to this value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn strong_count(this: &Self) -> usize {
+        this.header().strong.load(Ordering::Acquire)
+    }
+
+    /// The number of [`Weak`] pointers to this value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn weak_count(this: &Self) -> usize {
+        match this.header().weak.load(Ordering::Acquire) {
+            // The weak count is currently 'locked' by `get_mut`, so there are no Weak pointers.
+            usize::MAX => 0,
+            count => count - 1,
+        }
+    }
+
+    /// Returns true if the two SlimmerArc's point to the same allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr.as_ptr() == other.ptr.as_ptr()
+    }
+
+    /// Returns a mutable reference to the value, if there are no other SlimmerArc or Weak pointers to it.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if this.is_unique() {
+            let ptr = ptr_meta::from_raw_parts_mut(this.ptr.as_ptr(), Self::metadata(this));
+            // SAFETY: We are the only pointer to the value
+            Some(unsafe { &mut *ptr })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value, cloning it first if there are other SlimmerArc or Weak pointers to it.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn make_mut(this: &mut Self) -> &mut T
+    where
+        T: CloneUnsized,
+    {
+        if Self::get_mut(this).is_none() {
+            // SAFETY: The original SlimmerArc already checked the metadata on construction
+            *this = unsafe { Self::try_new(&**this).unwrap_unchecked() };
+        }
+        // SAFETY: A freshly created SlimmerArc is always unique
+        unsafe { Self::get_mut(this).unwrap_unchecked() }
+    }
+
+    /// Obtains a raw read-only (non-owned) pointer view of the contents of this SlimmerArc.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        ptr_meta::from_raw_parts(this.ptr.as_ptr(), SlimmerArc::metadata(this))
+    }
+
+    /// Retrieve access to the stored slimmer metadata value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn slim_metadata(this: &Self) -> SlimmerMetadata {
+        this.meta
+    }
+
+    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> <T as Pointee>::Metadata {
+        let aligned_len = SlimmerArc::slim_metadata(this);
+        aligned_len.expand()
+    }
+
+    fn header(&self) -> &ArcHeader {
+        // SAFETY: The header lives as long as there is at least one (strong or weak) pointer
+        unsafe { &*header::header::<ArcHeader>(self.ptr.as_ptr()) }
+    }
+
+    /// Checks whether there are no other strong or weak pointers.
+    ///
+    /// The weak count is 'locked' while checking the strong count,
+    /// to prevent a concurrent `Weak::upgrade` followed by a drop of that Weak from going unnoticed.
+    fn is_unique(&self) -> bool {
+        let header = self.header();
+        if header
+            .weak
+            .compare_exchange(1, usize::MAX, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            let unique = header.strong.load(Ordering::Acquire) == 1;
+            header.weak.store(1, Ordering::Release);
+            unique
+        } else {
+            false
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> Weak<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Attempts to turn this Weak pointer into a [`SlimmerArc`].
+    ///
+    /// Returns `None` if the value has already been dropped.
+    pub fn upgrade(&self) -> Option<SlimmerArc<T, SlimmerMetadata>> {
+        let strong = &self.header().strong;
+        let mut current = strong.load(Ordering::Relaxed);
+        loop {
+            if current == 0 {
+                return None;
+            }
+            if current >= header::MAX_REFCOUNT {
+                header::abort();
+            }
+            match strong.compare_exchange_weak(
+                current,
+                current + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(old) => current = old,
+            }
+        }
+        Some(SlimmerArc {
+            ptr: self.ptr,
+            meta: self.meta,
+            marker: PhantomData,
+        })
+    }
+
+    /// The number of strong ([`SlimmerArc`]) pointers to this value.
+    pub fn strong_count(&self) -> usize {
+        self.header().strong.load(Ordering::Acquire)
+    }
+
+    /// The number of Weak pointers to this value.
+    ///
+    /// Returns 0 if there are no remaining strong pointers.
+    pub fn weak_count(&self) -> usize {
+        let header = self.header();
+        if header.strong.load(Ordering::Acquire) == 0 {
+            0
+        } else {
+            match header.weak.load(Ordering::Acquire) {
+                usize::MAX => 0,
+                count => count - 1,
+            }
+        }
+    }
+
+    /// Returns true if the two Weak pointers point to the same allocation.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.ptr.as_ptr() == other.ptr.as_ptr()
+    }
+
+    fn header(&self) -> &ArcHeader {
+        // SAFETY: The header lives as long as there is at least one (strong or weak) pointer
+        unsafe { &*header::header::<ArcHeader>(self.ptr.as_ptr()) }
+    }
+}
+
+impl ArcHeader {
+    fn new() -> Self {
+        ArcHeader {
+            strong: AtomicUsize::new(1),
+            weak: AtomicUsize::new(1),
+        }
+    }
+}
+
+unsafe impl<T: Send + Sync, SlimmerMetadata: Send + Sync> Send for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<T: Send + Sync, SlimmerMetadata: Send + Sync> Sync for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<T: Send + Sync, SlimmerMetadata: Send + Sync> Send for Weak<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<T: Send + Sync, SlimmerMetadata: Send + Sync> Sync for Weak<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<T, SlimmerMetadata> Drop for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn drop(&mut self) {
+        if self.header().strong.fetch_sub(1, Ordering::Release) == 1 {
+            atomic::fence(Ordering::Acquire);
+            let ptr =
+                ptr_meta::from_raw_parts_mut::<T>(self.ptr.as_ptr(), SlimmerArc::metadata(self));
+            // SAFETY: We were the last strong pointer
+            unsafe { core::ptr::drop_in_place(ptr) };
+            // Drops the implicit weak pointer, freeing the allocation if there are no other Weak pointers.
+            let _weak = Weak::<T, SlimmerMetadata> {
+                ptr: self.ptr,
+                meta: self.meta,
+                marker: PhantomData,
+            };
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> Drop for Weak<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn drop(&mut self) {
+        if self.header().weak.fetch_sub(1, Ordering::Release) == 1 {
+            atomic::fence(Ordering::Acquire);
+            let metadata = self.meta.expand();
+            let ptr = ptr_meta::from_raw_parts::<T>(self.ptr.as_ptr(), metadata);
+            // SAFETY: The value has already been dropped,
+            // but its size and alignment only depend on its type and pointer metadata.
+            let value_layout = Layout::for_value(unsafe { &*ptr });
+            unsafe { header::dealloc::<ArcHeader>(self.ptr.as_ptr(), value_layout) };
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> Clone for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn clone(&self) -> Self {
+        let old = self.header().strong.fetch_add(1, Ordering::Relaxed);
+        // Like Arc, abort well before the count can actually wrap around
+        if old > header::MAX_REFCOUNT {
+            header::abort();
+        }
+        SlimmerArc {
+            ptr: self.ptr,
+            meta: self.meta,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> Clone for Weak<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn clone(&self) -> Self {
+        let old = self.header().weak.fetch_add(1, Ordering::Relaxed);
+        // Like Arc, abort well before the count can actually wrap around
+        if old > header::MAX_REFCOUNT {
+            header::abort();
+        }
+        Weak {
+            ptr: self.ptr,
+            meta: self.meta,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> From<SlimmerBox<T, SlimmerMetadata>> for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn from(boxed: SlimmerBox<T, SlimmerMetadata>) -> Self {
+        // SAFETY: The SlimmerBox already checked the metadata on construction
+        unsafe { Self::try_from_box(SlimmerBox::into_box(boxed)).unwrap_unchecked() }
+    }
+}
+
+impl<T, SlimmerMetadata> Deref for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        let ptr = ptr_meta::from_raw_parts(self.ptr.as_ptr(), SlimmerArc::metadata(self));
+        // SAFETY: Correct by construction
+        unsafe { &*ptr }
+    }
+}
+
+impl<T, SlimmerMetadata> core::borrow::Borrow<T> for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> AsRef<T> for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> Unpin for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<T, SlimmerMetadata> core::fmt::Debug for SlimmerArc<T, SlimmerMetadata>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T, SlimmerMetadata> core::fmt::Debug for Weak<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "(Weak)")
+    }
+}
+
+impl<T: PartialEq, SlimmerMetadata> PartialEq for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<T: PartialOrd, SlimmerMetadata> PartialOrd for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, SlimmerMetadata> Ord for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: Eq, SlimmerMetadata> Eq for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<T: core::hash::Hash, SlimmerMetadata> core::hash::Hash for SlimmerArc<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<SlimmerArc<str>>(), 12);
+        assert_eq!(core::mem::size_of::<Option<SlimmerArc<str>>>(), 12);
+        assert_eq!(core::mem::size_of::<Weak<[u64], u8>>(), 9);
+        assert_eq!(core::mem::size_of::<SlimmerArc<u64, ()>>(), 8);
+    }
+
+    #[test]
+    fn counts_and_weak() {
+        let arc: SlimmerArc<[u64]> = SlimmerArc::new(&[1, 2, 3][..]);
+        let arc2 = arc.clone();
+        let weak = SlimmerArc::downgrade(&arc);
+        assert_eq!(SlimmerArc::strong_count(&arc), 2);
+        assert_eq!(SlimmerArc::weak_count(&arc), 1);
+        assert!(SlimmerArc::ptr_eq(&arc, &arc2));
+
+        drop(arc);
+        drop(arc2);
+        assert_eq!(weak.strong_count(), 0);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let arc: SlimmerArc<str> = SlimmerArc::new("hello");
+        let handles: std::vec::Vec<_> = (0..4)
+            .map(|_| {
+                let arc = arc.clone();
+                std::thread::spawn(move || assert_eq!(&*arc, "hello"))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(SlimmerArc::strong_count(&arc), 1);
+    }
+
+    #[test]
+    fn make_mut_and_get_mut() {
+        let mut arc: SlimmerArc<str> = SlimmerArc::new("hello");
+        let arc2 = arc.clone();
+        assert!(SlimmerArc::get_mut(&mut arc).is_none());
+        SlimmerArc::make_mut(&mut arc).make_ascii_uppercase();
+        assert_eq!(&*arc, "HELLO");
+        assert_eq!(&*arc2, "hello");
+        assert!(SlimmerArc::get_mut(&mut arc).is_some());
+    }
+
+    #[test]
+    fn try_unwrap() {
+        let arc: SlimmerArc<[std::string::String]> =
+            SlimmerArc::from_box(vec!["a".into(), "b".into()].into_boxed_slice());
+        let weak = SlimmerArc::downgrade(&arc);
+        let arc2 = arc.clone();
+        let arc = SlimmerArc::try_unwrap(arc).unwrap_err();
+        drop(arc2);
+        let boxed = SlimmerArc::try_unwrap(arc).unwrap();
+        assert_eq!(&*boxed, &["a", "b"]);
+        assert!(weak.upgrade().is_none());
+    }
+}
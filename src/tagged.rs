@@ -0,0 +1,451 @@
+//! A SlimmerBox which stores a small tag in the (always zero) low bits of its pointer.
+//!
+//! See [`TaggedSlimmerBox`] for more information.
+use allocator_api2::alloc::Global;
+use core::{
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+use ptr_meta::Pointee;
+
+use crate::{CloneUnsized, DefaultSlimmerMetadata, SlimMetadata, SlimmerBox, SlimmerPointee};
+
+/// Trait for (possibly unsized) types whose minimal alignment is known at compile time.
+///
+/// Implemented for:
+/// - Normal sized T (its `align_of`).
+/// - the dynamicaly-sized slice type `[T]` (the `align_of` of its elements).
+/// - the dynamically-sized string slice type `str` (always 1).
+///
+/// # Safety
+///
+/// Every (possibly empty) value of this type must be at least `ALIGN`-aligned,
+/// and `ALIGN` must be a power of two.
+pub unsafe trait StaticAlign {
+    /// The minimal alignment of all values of this type.
+    const ALIGN: usize;
+}
+
+unsafe impl<T> StaticAlign for T {
+    const ALIGN: usize = core::mem::align_of::<T>();
+}
+
+unsafe impl<T> StaticAlign for [T] {
+    const ALIGN: usize = core::mem::align_of::<T>();
+}
+
+unsafe impl StaticAlign for str {
+    const ALIGN: usize = 1;
+}
+
+/// A [`SlimmerBox`] which stores a `BITS`-bit tag in the low bits of its pointer.
+///
+/// Since a `T` is always aligned to (at least) `align_of::<T>()`,
+/// the lowest `log2(align_of::<T>())` bits of a pointer to it are always zero.
+/// A TaggedSlimmerBox stores a small tag in those bits,
+/// so it is exactly as large as the SlimmerBox it wraps.
+///
+/// This can for instance be used to store an enum discriminant without growing past the size of a SlimmerBox:
+///
+/// ```rust
+/// use slimmer_box::{SlimmerBox, TaggedSlimmerBox};
+///
+/// let mut tagged: TaggedSlimmerBox<[u64], u32, 3> = TaggedSlimmerBox::new(&[1, 2, 3][..], 5);
+/// assert_eq!(core::mem::size_of_val(&tagged), 12);
+/// assert_eq!(TaggedSlimmerBox::tag(&tagged), 5);
+/// assert_eq!(&*tagged, &[1, 2, 3]);
+///
+/// TaggedSlimmerBox::set_tag(&mut tagged, 7);
+/// let (boxed, tag): (SlimmerBox<[u64]>, usize) = TaggedSlimmerBox::into_parts(tagged);
+/// assert_eq!(tag, 7);
+/// assert_eq!(&*boxed, &[1, 2, 3]);
+/// ```
+///
+/// Whether `T` has enough free bits is checked at compile time:
+///
+/// ```compile_fail
+/// use slimmer_box::TaggedSlimmerBox;
+///
+/// // A u16 is only 2-aligned, so only has one free bit
+/// let tagged: TaggedSlimmerBox<[u16], u32, 2> = TaggedSlimmerBox::new(&[1, 2, 3][..], 0);
+/// ```
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct TaggedSlimmerBox<
+    T,
+    SlimmerMetadata = <T as DefaultSlimmerMetadata>::Metadata,
+    const BITS: u8 = 1,
+> where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    // Stores `address | tag`
+    ptr: NonNull<()>,
+    meta: SlimmerMetadata,
+    marker: PhantomData<T>,
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Evaluating this constant fails to compile if `T` is not aligned enough to store `BITS` bits.
+    const ASSERT_ENOUGH_FREE_BITS: () = assert!(
+        (BITS as u32) <= T::ALIGN.trailing_zeros(),
+        "The alignment of T does not leave enough free bits to store the tag"
+    );
+
+    /// The mask of the bits that are used to store the tag.
+    const TAG_MASK: usize = (1 << BITS) - 1;
+
+    /// Creates a new TaggedSlimmerBox from the given value (which may be a slice, string or other dynamically sized type) and tag.
+    ///
+    /// Panics if the value's Metadata is too large to fit in SlimmerMetadata,
+    /// or if the tag does not fit in `BITS` bits.
+    pub fn new(value: &T, tag: usize) -> Self
+    where
+        T: CloneUnsized,
+    {
+        Self::from_slimmer_box(SlimmerBox::new(value), tag)
+    }
+
+    /// Turns a SlimmerBox into a TaggedSlimmerBox with the given tag.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    ///
+    /// Panics if the tag does not fit in `BITS` bits.
+    pub fn from_slimmer_box(boxed: SlimmerBox<T, SlimmerMetadata>, tag: usize) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ASSERT_ENOUGH_FREE_BITS;
+        Self::check_tag(tag);
+        let meta = SlimmerBox::slim_metadata(&boxed);
+        let ptr = SlimmerBox::into_raw(boxed) as *mut u8;
+        // NOTE: Since the low bits of the address are zero, this addition is the same as a bitwise OR.
+        // Using pointer arithmetic rather than integer casts keeps the pointer's provenance intact.
+        let tagged = ptr.wrapping_add(tag) as *mut ();
+        Self {
+            // SAFETY: SlimmerBox ensures the pointer is non-null, and adding to the low bits keeps it that way
+            ptr: unsafe { NonNull::new_unchecked(tagged) },
+            meta,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the tag stored in the low bits of the pointer.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn tag(this: &Self) -> usize {
+        this.ptr.as_ptr() as usize & Self::TAG_MASK
+    }
+
+    /// Replaces the tag stored in the low bits of the pointer.
+    ///
+    /// Panics if the tag does not fit in `BITS` bits.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn set_tag(this: &mut Self, tag: usize) {
+        Self::check_tag(tag);
+        let untagged = TaggedSlimmerBox::untagged_ptr(this) as *mut u8;
+        // SAFETY: Adding to the low bits of a non-null pointer keeps it non-null
+        this.ptr = unsafe { NonNull::new_unchecked(untagged.wrapping_add(tag) as *mut ()) };
+    }
+
+    /// Splits the TaggedSlimmerBox into the SlimmerBox and the tag.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_parts(this: Self) -> (SlimmerBox<T, SlimmerMetadata>, usize) {
+        let tag = TaggedSlimmerBox::tag(&this);
+        let boxed = TaggedSlimmerBox::to_slimmer_box(&this);
+        core::mem::forget(this);
+        (ManuallyDrop::into_inner(boxed), tag)
+    }
+
+    /// Turns the TaggedSlimmerBox back into a normal SlimmerBox, discarding the tag.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_slimmer_box(this: Self) -> SlimmerBox<T, SlimmerMetadata> {
+        TaggedSlimmerBox::into_parts(this).0
+    }
+
+    /// Obtains a raw read-only (non-owned) pointer view of the contents of this TaggedSlimmerBox.
+    ///
+    /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        ptr_meta::from_raw_parts(
+            TaggedSlimmerBox::untagged_ptr(this),
+            TaggedSlimmerBox::metadata(this),
+        )
+    }
+
+    /// Retrieve access to the stored slimmer metadata value.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn slim_metadata(this: &Self) -> SlimmerMetadata {
+        this.meta
+    }
+
+    /// Returns the outcome of converting the stored SlimmerMetadata value back into its original Metadata form.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> <T as Pointee>::Metadata {
+        let aligned_len = TaggedSlimmerBox::slim_metadata(this);
+        aligned_len.expand()
+    }
+
+    fn check_tag(tag: usize) {
+        assert!(
+            tag & !Self::TAG_MASK == 0,
+            "Tag {} does not fit in {} bits",
+            tag,
+            BITS
+        );
+    }
+
+    fn untagged_ptr(this: &Self) -> *mut () {
+        let tag = TaggedSlimmerBox::tag(this);
+        (this.ptr.as_ptr() as *mut u8).wrapping_sub(tag) as *mut ()
+    }
+
+    /// Builds the wrapped SlimmerBox, which must not be dropped while `this` is still in use.
+    fn to_slimmer_box(this: &Self) -> ManuallyDrop<SlimmerBox<T, SlimmerMetadata>> {
+        ManuallyDrop::new(SlimmerBox {
+            // SAFETY: Removing the tag results in the original non-null pointer
+            ptr: unsafe { NonNull::new_unchecked(TaggedSlimmerBox::untagged_ptr(this)) },
+            meta: this.meta,
+            alloc: ManuallyDrop::new(Global),
+            marker: PhantomData,
+        })
+    }
+}
+
+unsafe impl<T: Send, SlimmerMetadata: Send, const BITS: u8> Send
+    for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+unsafe impl<T: Sync, SlimmerMetadata: Sync, const BITS: u8> Sync
+    for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> Drop for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn drop(&mut self) {
+        let boxed = TaggedSlimmerBox::to_slimmer_box(self);
+        core::mem::drop(ManuallyDrop::into_inner(boxed));
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> From<TaggedSlimmerBox<T, SlimmerMetadata, BITS>>
+    for SlimmerBox<T, SlimmerMetadata>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Discards the tag.
+    fn from(tagged: TaggedSlimmerBox<T, SlimmerMetadata, BITS>) -> Self {
+        TaggedSlimmerBox::into_slimmer_box(tagged)
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> Deref for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Correct by construction
+        unsafe { &*TaggedSlimmerBox::to_ptr(self) }
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> DerefMut for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: Correct by construction, and we are borrowed mutably
+        unsafe { &mut *(TaggedSlimmerBox::to_ptr(self) as *mut T) }
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> AsRef<T> for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> AsMut<T> for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> Unpin for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> Clone for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: CloneUnsized,
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    /// Clones the value, keeping the same tag.
+    fn clone(&self) -> Self {
+        let boxed = SlimmerBox::clone(&TaggedSlimmerBox::to_slimmer_box(self));
+        Self::from_slimmer_box(boxed, TaggedSlimmerBox::tag(self))
+    }
+}
+
+impl<T, SlimmerMetadata, const BITS: u8> core::fmt::Debug
+    for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedSlimmerBox")
+            .field("value", &&**self)
+            .field("tag", &TaggedSlimmerBox::tag(self))
+            .finish()
+    }
+}
+
+/// Two TaggedSlimmerBoxes are equal if both their tags and their values are equal.
+impl<T: PartialEq, SlimmerMetadata, const BITS: u8> PartialEq
+    for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        TaggedSlimmerBox::tag(self) == TaggedSlimmerBox::tag(other)
+            && PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<T: Eq, SlimmerMetadata, const BITS: u8> Eq for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+}
+
+impl<T: core::hash::Hash, SlimmerMetadata, const BITS: u8> core::hash::Hash
+    for TaggedSlimmerBox<T, SlimmerMetadata, BITS>
+where
+    T: ?Sized,
+    T: StaticAlign,
+    T: SlimmerPointee<SlimmerMetadata>,
+    SlimmerMetadata: SlimMetadata<<T as Pointee>::Metadata>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        TaggedSlimmerBox::tag(self).hash(state);
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<TaggedSlimmerBox<[u64]>>(), 12);
+        assert_eq!(
+            core::mem::size_of::<Option<TaggedSlimmerBox<[u64], u32, 3>>>(),
+            12
+        );
+        assert_eq!(core::mem::size_of::<TaggedSlimmerBox<u32, (), 2>>(), 8);
+    }
+
+    #[test]
+    fn tags() {
+        let mut tagged: TaggedSlimmerBox<u64, (), 3> = TaggedSlimmerBox::new(&42, 0);
+        for tag in 0..8 {
+            TaggedSlimmerBox::set_tag(&mut tagged, tag);
+            assert_eq!(TaggedSlimmerBox::tag(&tagged), tag);
+            assert_eq!(*tagged, 42);
+        }
+        *tagged += 1;
+        let cloned = tagged.clone();
+        assert_eq!(cloned, tagged);
+        let (boxed, tag) = TaggedSlimmerBox::into_parts(cloned);
+        assert_eq!((*boxed, tag), (43, 7));
+    }
+
+    #[test]
+    fn empty_slice() {
+        let tagged: TaggedSlimmerBox<[u32], u8, 2> = TaggedSlimmerBox::new(&[][..], 3);
+        assert_eq!(TaggedSlimmerBox::tag(&tagged), 3);
+        assert!(tagged.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn tag_too_large() {
+        let _ = TaggedSlimmerBox::<[u32], u8, 2>::new(&[1][..], 4);
+    }
+}
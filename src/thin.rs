@@ -0,0 +1,410 @@
+//! A box for slices and strings whose length is stored inside the heap allocation.
+//!
+//! See [`ThinSlimmerBox<T>`] for more information.
+use alloc::boxed::Box;
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+use ptr_meta::Pointee;
+
+use crate::{
+    header, CloneUnsized, PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox, SlimmerPointee,
+};
+
+/// An alternative to [`SlimmerBox<T>`] for slices and strings which is exactly one word in size.
+///
+/// Rather than storing the length right next to the pointer,
+/// a `ThinSlimmerBox` stores the length in a small header inside the heap allocation, right in front of the value.
+/// This means that reading the length requires an extra memory access,
+/// but the handle itself is as small as a pointer to a sized type:
+///
+/// ```rust
+/// use slimmer_box::ThinSlimmerBox;
+///
+/// let thin: ThinSlimmerBox<str> = ThinSlimmerBox::new("hello, world!");
+/// assert_eq!(core::mem::size_of_val(&thin), 8);
+/// assert_eq!(core::mem::size_of::<Option<ThinSlimmerBox<[u64]>>>(), 8);
+/// assert_eq!(&*thin, "hello, world!");
+/// ```
+///
+/// Since the length is stored as a full `usize` there is no maximum length,
+/// which is why construction never fails.
+///
+/// This is a good choice for values whose length is rarely read, for instance because they are mostly passed around.
+pub struct ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    ptr: NonNull<()>,
+    marker: PhantomData<T>,
+}
+
+impl<T> ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    /// Creates a new ThinSlimmerBox from the given slice or string.
+    ///
+    /// This involves cloning the slice (which will clone all elements one by one)
+    /// and as such only works for types whose contents are cloneable.
+    /// Otherwise, use `from_box`.
+    pub fn new(value: &T) -> Self
+    where
+        T: CloneUnsized,
+    {
+        let meta = ptr_meta::metadata(value);
+        // SAFETY: usize is not zero-sized
+        let ptr = unsafe { header::alloc_clone::<usize, T>(value) };
+        // SAFETY: The header was allocated but not yet initialized
+        unsafe { header::header::<usize>(ptr.as_ptr()).write(meta) };
+        Self {
+            ptr,
+            marker: PhantomData,
+        }
+    }
+
+    /// Turns a Box into a ThinSlimmerBox.
+    ///
+    /// This moves the value into a new allocation which also contains the length.
+    pub fn from_box(boxed: Box<T>) -> Self {
+        let meta = ptr_meta::metadata(&*boxed);
+        let value_layout = Layout::for_value(&*boxed);
+        let box_ptr = Box::into_raw(boxed);
+        unsafe {
+            // SAFETY: usize is not zero-sized
+            let ptr = header::alloc::<usize>(value_layout);
+            header::header::<usize>(ptr.as_ptr()).write(meta);
+            // SAFETY: The value is moved (rather than cloned) into the new allocation,
+            // after which the old allocation is freed without dropping the value.
+            core::ptr::copy_nonoverlapping(
+                box_ptr as *const u8,
+                ptr.as_ptr() as *mut u8,
+                value_layout.size(),
+            );
+            if value_layout.size() != 0 {
+                alloc::alloc::dealloc(box_ptr as *mut u8, value_layout);
+            }
+            Self {
+                ptr,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    /// Turns a ThinSlimmerBox into a Box.
+    ///
+    /// This moves the value into a new allocation which no longer contains the length.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_box(this: Self) -> Box<T> {
+        let value_layout = Layout::for_value(&*this);
+        let box_ptr = if value_layout.size() == 0 {
+            value_layout.align() as *mut u8
+        } else {
+            let box_ptr = unsafe { alloc::alloc::alloc(value_layout) };
+            if box_ptr.is_null() {
+                alloc::alloc::handle_alloc_error(value_layout)
+            }
+            box_ptr
+        };
+        let meta = ThinSlimmerBox::metadata(&this);
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                this.ptr.as_ptr() as *const u8,
+                box_ptr,
+                value_layout.size(),
+            );
+            header::dealloc::<usize>(this.ptr.as_ptr(), value_layout);
+        }
+        core::mem::forget(this);
+        // SAFETY: The value was moved into an allocation with the layout Box expects.
+        unsafe { Box::from_raw(ptr_meta::from_raw_parts_mut(box_ptr as *mut (), meta)) }
+    }
+
+    /// Turns a SlimmerBox into a ThinSlimmerBox.
+    ///
+    /// This moves the value into a new allocation which also contains the length.
+    pub fn from_slimmer_box<SlimmerMetadata>(boxed: SlimmerBox<T, SlimmerMetadata>) -> Self
+    where
+        T: SlimmerPointee<SlimmerMetadata>,
+        SlimmerMetadata: SlimMetadata<usize>,
+    {
+        Self::from_box(SlimmerBox::into_box(boxed))
+    }
+
+    /// Turns a ThinSlimmerBox into a SlimmerBox.
+    ///
+    /// This moves the value into a new allocation which no longer contains the length.
+    ///
+    /// Panics if the length is too large to fit in SlimmerMetadata.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn into_slimmer_box<SlimmerMetadata>(this: Self) -> SlimmerBox<T, SlimmerMetadata>
+    where
+        T: SlimmerPointee<SlimmerMetadata>,
+        SlimmerMetadata: SlimMetadata<usize>,
+    {
+        Self::try_into_slimmer_box(this).unwrap()
+    }
+
+    /// Variant of `into_slimmer_box` which will return an error if the length is too large instead of panicing.
+    ///
+    /// On error, the ThinSlimmerBox is handed back inside the error.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn try_into_slimmer_box<SlimmerMetadata>(
+        this: Self,
+    ) -> Result<
+        SlimmerBox<T, SlimmerMetadata>,
+        PointerMetadataDoesNotFitError<T, SlimmerMetadata, ThinSlimmerBox<T>>,
+    >
+    where
+        T: SlimmerPointee<SlimmerMetadata>,
+        SlimmerMetadata: SlimMetadata<usize>,
+    {
+        let meta = ThinSlimmerBox::metadata(&this);
+        if SlimmerMetadata::try_compress(meta).is_none() {
+            return Err(PointerMetadataDoesNotFitError::new(meta, this));
+        }
+        // SAFETY: Checked above
+        Ok(unsafe { SlimmerBox::from_box_unchecked(ThinSlimmerBox::into_box(this)) })
+    }
+
+    /// Obtains a raw read-only (non-owned) pointer view of the contents of this ThinSlimmerBox.
+    ///
+    /// The resulting pointer is guaranteed to be a valid instance of T and non-null.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn to_ptr(this: &Self) -> *const T {
+        ptr_meta::from_raw_parts(this.ptr.as_ptr(), ThinSlimmerBox::metadata(this))
+    }
+
+    /// Reads the metadata (the length) stored in the heap allocation.
+    ///
+    /// Not an associated function to not interfere with Deref, so use fully qualified syntax to call it.
+    pub fn metadata(this: &Self) -> usize {
+        // SAFETY: The header is written on construction and lives as long as the ThinSlimmerBox
+        unsafe { *header::header::<usize>(this.ptr.as_ptr()) }
+    }
+}
+
+unsafe impl<T: Send> Send for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+unsafe impl<T: Sync> Sync for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+impl<T> Drop for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn drop(&mut self) {
+        let ptr: *mut T =
+            ptr_meta::from_raw_parts_mut(self.ptr.as_ptr(), ThinSlimmerBox::metadata(self));
+        unsafe {
+            let value_layout = Layout::for_value(&*ptr);
+            core::ptr::drop_in_place(ptr);
+            header::dealloc::<usize>(self.ptr.as_ptr(), value_layout);
+        }
+    }
+}
+
+impl<T> Deref for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Correct by construction
+        unsafe { &*ThinSlimmerBox::to_ptr(self) }
+    }
+}
+
+impl<T> DerefMut for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let ptr = ptr_meta::from_raw_parts_mut(self.ptr.as_ptr(), ThinSlimmerBox::metadata(self));
+        // SAFETY: Correct by construction
+        unsafe { &mut *ptr }
+    }
+}
+
+impl<T> core::borrow::Borrow<T> for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T> core::borrow::BorrowMut<T> for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T> AsRef<T> for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T> AsMut<T> for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T> Unpin for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+impl<T> Clone for ThinSlimmerBox<T>
+where
+    T: CloneUnsized,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn clone(&self) -> Self {
+        ThinSlimmerBox::new(self.deref())
+    }
+}
+
+impl<T> core::fmt::Debug for ThinSlimmerBox<T>
+where
+    T: core::fmt::Debug,
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord> Ord for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: Eq> Eq for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for ThinSlimmerBox<T>
+where
+    T: ?Sized,
+    T: Pointee<Metadata = usize>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<ThinSlimmerBox<str>>(), 8);
+        assert_eq!(core::mem::size_of::<Option<ThinSlimmerBox<[u64]>>>(), 8);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let thin: ThinSlimmerBox<[u64]> = ThinSlimmerBox::new(&[1, 2, 3][..]);
+        assert_eq!(ThinSlimmerBox::metadata(&thin), 3);
+        let slimmer: SlimmerBox<[u64], u8> = ThinSlimmerBox::into_slimmer_box(thin.clone());
+        assert_eq!(&*slimmer, &*thin);
+        let thin2 = ThinSlimmerBox::from_slimmer_box(slimmer);
+        assert_eq!(thin, thin2);
+        let boxed = ThinSlimmerBox::into_box(thin2);
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn empty_and_overaligned() {
+        #[derive(Clone, Debug, PartialEq)]
+        #[repr(align(32))]
+        struct Aligned(u8);
+
+        let thin: ThinSlimmerBox<[Aligned]> = ThinSlimmerBox::new(&[Aligned(1), Aligned(2)][..]);
+        assert_eq!(thin.as_ptr() as usize % 32, 0);
+        assert_eq!(&*thin, &[Aligned(1), Aligned(2)]);
+
+        let empty: ThinSlimmerBox<str> = ThinSlimmerBox::new("");
+        assert_eq!(&*empty, "");
+    }
+
+    #[test]
+    fn too_long() {
+        let thin: ThinSlimmerBox<[u8]> = ThinSlimmerBox::new(&[0; 300][..]);
+        assert!(ThinSlimmerBox::try_into_slimmer_box::<u8>(thin).is_err());
+    }
+}
@@ -0,0 +1,933 @@
+//! A growable vector whose length and capacity are 'slimmer'.
+//!
+//! See [`SlimmerVec<T>`] for more information.
+use allocator_api2::alloc::Global;
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut, RangeBounds},
+    ptr::NonNull,
+};
+
+use ptr_meta::Pointee;
+
+use crate::{PointerMetadataDoesNotFitError, SlimMetadata, SlimmerBox, SlimmerPointee};
+
+/// A growable alternative to [`Vec<T>`](alloc::vec::Vec) which stores its length and capacity in a (smaller) `SlimmerMetadata`.
+///
+/// A normal `Vec<T>` takes up 24 bytes on 64-bit systems (pointer, capacity and length).
+/// A `SlimmerVec<T, u32>` only takes up 16 bytes, and a `SlimmerVec<T, u16>` only 12.
+///
+/// A SlimmerVec will never grow beyond the maximum that fits in `SlimmerMetadata`.
+/// Use [`SlimmerVec::try_reserve`] to be notified of this with an error
+/// (other methods that grow the vector will panic instead).
+///
+/// Once you are done building, [`SlimmerVec::into_slimmer_box`] turns the vector into a [`SlimmerBox<[T], SlimmerMetadata>`]
+/// without copying the elements.
+///
+/// ```rust
+/// use slimmer_box::{SlimmerBox, SlimmerVec};
+///
+/// let mut vec: SlimmerVec<u64> = SlimmerVec::new();
+/// assert_eq!(core::mem::size_of_val(&vec), 16);
+/// vec.push(1);
+/// vec.extend([2, 3, 4]);
+/// vec.retain(|x| x % 2 == 0);
+///
+/// let boxed: SlimmerBox<[u64]> = vec.into_slimmer_box();
+/// assert_eq!(&*boxed, &[2, 4]);
+/// ```
+#[repr(packed)]
+#[allow(clippy::repr_packed_without_abi)]
+pub struct SlimmerVec<T, SlimmerMetadata = u32>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    ptr: NonNull<T>,
+    len: SlimmerMetadata,
+    cap: SlimmerMetadata,
+    marker: PhantomData<T>,
+}
+
+impl<T, SlimmerMetadata> SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Creates a new, empty SlimmerVec.
+    ///
+    /// This does not allocate.
+    pub fn new() -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            len: Self::to_slim(0).expect("SlimmerMetadata cannot represent zero"),
+            cap: Self::to_slim(0).expect("SlimmerMetadata cannot represent zero"),
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty SlimmerVec with room for at least `capacity` elements.
+    ///
+    /// Panics if `capacity` does not fit in SlimmerMetadata.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::try_with_capacity(capacity).unwrap()
+    }
+
+    /// Variant of `with_capacity` which will return an error if `capacity` does not fit instead of panicing.
+    pub fn try_with_capacity(
+        capacity: usize,
+    ) -> Result<Self, PointerMetadataDoesNotFitError<[T], SlimmerMetadata>> {
+        let mut vec = Self::new();
+        vec.try_reserve_exact(capacity)?;
+        Ok(vec)
+    }
+
+    /// Turns a SlimmerBox into a SlimmerVec.
+    ///
+    /// This is a fast constant-time operation that needs no allocation.
+    pub fn from_slimmer_box(boxed: SlimmerBox<[T], SlimmerMetadata>) -> Self
+    {
+        let meta = SlimmerBox::slim_metadata(&boxed);
+        let ptr = SlimmerBox::into_raw(boxed) as *mut T;
+        let ptr = if core::mem::size_of::<T>() == 0 || Self::from_slim(meta) == 0 {
+            // NOTE: This allocation, if any, is zero-sized and was not made by the global allocator
+            NonNull::dangling()
+        } else {
+            // SAFETY: SlimmerBox ensures the pointer is non-null
+            unsafe { NonNull::new_unchecked(ptr) }
+        };
+        Self {
+            ptr,
+            len: meta,
+            cap: meta,
+            marker: PhantomData,
+        }
+    }
+
+    /// Turns the SlimmerVec into a SlimmerBox.
+    ///
+    /// Any excess capacity is released first (using `realloc`, which can usually be done in place).
+    /// The elements themselves are never copied one by one.
+    pub fn into_slimmer_box(mut self) -> SlimmerBox<[T], SlimmerMetadata>
+    {
+        self.shrink_to_fit();
+        let len = self.len;
+        let ptr = self.ptr;
+        core::mem::forget(self);
+        SlimmerBox {
+            ptr: ptr.cast(),
+            meta: len,
+            alloc: ManuallyDrop::new(Global),
+            marker: PhantomData,
+        }
+    }
+
+    /// The number of elements in the vector.
+    pub fn len(&self) -> usize {
+        Self::from_slim(self.len)
+    }
+
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of elements the vector can hold without reallocating.
+    ///
+    /// For zero-sized `T` this is the maximum length that fits in SlimmerMetadata.
+    pub fn capacity(&self) -> usize {
+        if core::mem::size_of::<T>() == 0 {
+            return SlimmerMetadata::MAX;
+        }
+        Self::from_slim(self.cap)
+    }
+
+    /// Extracts a slice containing the entire vector.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: The first `len` elements are always initialized
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len()) }
+    }
+
+    /// Extracts a mutable slice containing the entire vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: The first `len` elements are always initialized
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len()) }
+    }
+
+    /// Returns a raw pointer to the vector's buffer.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns a raw mutable pointer to the vector's buffer.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// Sets the length of the vector.
+    ///
+    /// # Safety
+    /// - `new_len` must be less than or equal to `capacity()`.
+    /// - The elements at `old_len..new_len` must be initialized.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        // NOTE: Any length up to the capacity is guaranteed to fit.
+        self.len = Self::to_slim(new_len).unwrap_unchecked();
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// Panics if the new capacity does not fit in SlimmerMetadata.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional).unwrap()
+    }
+
+    /// Variant of `reserve` which will return an error if the new capacity does not fit instead of panicing.
+    ///
+    /// The capacity grows exponentially, but never beyond what fits in SlimmerMetadata.
+    pub fn try_reserve(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), PointerMetadataDoesNotFitError<[T], SlimmerMetadata>> {
+        let required = self.required_capacity(additional)?;
+        if required <= self.capacity() {
+            return Ok(());
+        }
+        let doubled = core::cmp::max(self.capacity().saturating_mul(2), 4);
+        let new_cap = core::cmp::max(doubled, required);
+        if Self::to_slim(new_cap).is_some() && Layout::array::<T>(new_cap).is_ok() {
+            self.grow_to(new_cap);
+        } else {
+            self.grow_to(required);
+        }
+        Ok(())
+    }
+
+    /// Reserves capacity for exactly `additional` more elements.
+    ///
+    /// Panics if the new capacity does not fit in SlimmerMetadata.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.try_reserve_exact(additional).unwrap()
+    }
+
+    /// Variant of `reserve_exact` which will return an error if the new capacity does not fit instead of panicing.
+    pub fn try_reserve_exact(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), PointerMetadataDoesNotFitError<[T], SlimmerMetadata>> {
+        let required = self.required_capacity(additional)?;
+        if required > self.capacity() {
+            self.grow_to(required);
+        }
+        Ok(())
+    }
+
+    /// Shrinks the capacity of the vector to its length.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self.len();
+        if core::mem::size_of::<T>() == 0 || len == self.capacity() {
+            return;
+        }
+        // SAFETY: Only called when capacity > len >= 0, so there is an allocation
+        unsafe {
+            let old_layout = Layout::array::<T>(self.capacity()).unwrap_unchecked();
+            if len == 0 {
+                alloc::alloc::dealloc(self.ptr.as_ptr() as *mut u8, old_layout);
+                self.ptr = NonNull::dangling();
+            } else {
+                let new_layout = Layout::array::<T>(len).unwrap_unchecked();
+                let ptr = alloc::alloc::realloc(
+                    self.ptr.as_ptr() as *mut u8,
+                    old_layout,
+                    new_layout.size(),
+                );
+                self.ptr = match NonNull::new(ptr as *mut T) {
+                    Some(ptr) => ptr,
+                    None => alloc::alloc::handle_alloc_error(new_layout),
+                };
+            }
+        }
+        self.cap = self.len;
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// Panics if the new length does not fit in SlimmerMetadata.
+    pub fn push(&mut self, value: T) {
+        self.reserve(1);
+        let len = self.len();
+        // SAFETY: We just reserved room for this element
+        unsafe {
+            self.ptr.as_ptr().add(len).write(value);
+            self.set_len(len + 1);
+        }
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        // SAFETY: The element at len - 1 is initialized, and no longer considered part of the vector
+        unsafe {
+            self.set_len(len - 1);
+            Some(self.ptr.as_ptr().add(len - 1).read())
+        }
+    }
+
+    /// Inserts an element at position `index`, shifting all elements after it to the right.
+    ///
+    /// Panics if `index > len`, or if the new length does not fit in SlimmerMetadata.
+    pub fn insert(&mut self, index: usize, element: T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+        self.reserve(1);
+        // SAFETY: We just reserved room for this element, and index is in bounds
+        unsafe {
+            let ptr = self.ptr.as_ptr().add(index);
+            core::ptr::copy(ptr, ptr.add(1), len - index);
+            ptr.write(element);
+            self.set_len(len + 1);
+        }
+    }
+
+    /// Removes and returns the element at position `index`, shifting all elements after it to the left.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+        // SAFETY: index is in bounds
+        unsafe {
+            let ptr = self.ptr.as_ptr().add(index);
+            let result = ptr.read();
+            core::ptr::copy(ptr.add(1), ptr, len - index - 1);
+            self.set_len(len - 1);
+            result
+        }
+    }
+
+    /// Removes and returns the element at position `index`, replacing it with the last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "swap_remove index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+        // SAFETY: index is in bounds
+        unsafe {
+            let ptr = self.ptr.as_ptr();
+            let result = ptr.add(index).read();
+            core::ptr::copy(ptr.add(len - 1), ptr.add(index), 1);
+            self.set_len(len - 1);
+            result
+        }
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    ///
+    /// Does nothing if `len` is greater than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        // SAFETY: The elements at len..old_len are initialized,
+        // and the length is updated first in case dropping them panics.
+        unsafe {
+            self.set_len(len);
+            let tail =
+                core::ptr::slice_from_raw_parts_mut(self.ptr.as_ptr().add(len), old_len - len);
+            core::ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Removes all elements from the vector, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Retains only the elements for which `keep` returns true, preserving their order.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        // Elements are moved to the front one by one.
+        // While doing this, the vector's length is set to the number of retained elements,
+        // so if `keep` or a `drop` panics, the remaining elements are leaked rather than double-dropped.
+        unsafe { self.set_len(0) };
+        let ptr = self.ptr.as_ptr();
+        let mut retained = 0;
+        for index in 0..len {
+            // SAFETY: Every element in index..len is still initialized and is visited exactly once
+            unsafe {
+                let current = ptr.add(index);
+                if keep(&*current) {
+                    if index != retained {
+                        core::ptr::copy_nonoverlapping(current, ptr.add(retained), 1);
+                    }
+                    retained += 1;
+                    self.set_len(retained);
+                } else {
+                    core::ptr::drop_in_place(current);
+                }
+            }
+        }
+    }
+
+    /// Removes the elements in `range` from the vector, returning them as an iterator.
+    ///
+    /// Elements that are not consumed from the iterator are dropped when it is dropped.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, SlimmerMetadata>
+    where
+        R: RangeBounds<usize>,
+    {
+        use core::ops::Bound;
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end,
+            "drain start (is {}) should be <= end (is {})",
+            start,
+            end
+        );
+        assert!(
+            end <= len,
+            "drain end (is {}) should be <= len (is {})",
+            end,
+            len
+        );
+
+        // The drained elements (and the tail) are not considered part of the vector while draining.
+        unsafe { self.set_len(start) };
+        Drain {
+            front: start,
+            back: end,
+            tail_start: end,
+            tail_len: len - end,
+            vec: self,
+        }
+    }
+
+    /// Appends all elements of `other` to the vector, cloning them.
+    ///
+    /// Panics if the new length does not fit in SlimmerMetadata.
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(other.len());
+        for element in other {
+            self.push(element.clone());
+        }
+    }
+
+    fn required_capacity(
+        &self,
+        additional: usize,
+    ) -> Result<usize, PointerMetadataDoesNotFitError<[T], SlimmerMetadata>> {
+        match self.len().checked_add(additional) {
+            Some(required) if Self::to_slim(required).is_some() => Ok(required),
+            required => Err(PointerMetadataDoesNotFitError::new(
+                required.unwrap_or(usize::MAX),
+                (),
+            )),
+        }
+    }
+
+    /// Reallocates the buffer to exactly `new_cap`, which has to fit in SlimmerMetadata and be larger than the current capacity.
+    fn grow_to(&mut self, new_cap: usize) {
+        // SAFETY: Callers check that new_cap fits in SlimmerMetadata
+        let slim_cap = unsafe { Self::to_slim(new_cap).unwrap_unchecked() };
+        if core::mem::size_of::<T>() != 0 {
+            let new_layout = Layout::array::<T>(new_cap).expect("capacity overflow");
+            let ptr = unsafe {
+                if self.capacity() == 0 {
+                    alloc::alloc::alloc(new_layout)
+                } else {
+                    let old_layout = Layout::array::<T>(self.capacity()).unwrap_unchecked();
+                    alloc::alloc::realloc(
+                        self.ptr.as_ptr() as *mut u8,
+                        old_layout,
+                        new_layout.size(),
+                    )
+                }
+            };
+            self.ptr = match NonNull::new(ptr as *mut T) {
+                Some(ptr) => ptr,
+                None => alloc::alloc::handle_alloc_error(new_layout),
+            };
+        }
+        self.cap = slim_cap;
+    }
+
+    fn to_slim(len: usize) -> Option<SlimmerMetadata> {
+        SlimmerMetadata::try_compress(len)
+    }
+
+    fn from_slim(meta: SlimmerMetadata) -> usize {
+        meta.expand()
+    }
+}
+
+/// A draining iterator for [`SlimmerVec<T>`].
+///
+/// Created by [`SlimmerVec::drain`].
+pub struct Drain<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    vec: &'a mut SlimmerVec<T, SlimmerMetadata>,
+    front: usize,
+    back: usize,
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<'a, T, SlimmerMetadata> Drain<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    /// Returns the remaining (not yet yielded) elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: The elements in front..back are initialized and not yet yielded
+        unsafe {
+            core::slice::from_raw_parts(
+                self.vec.ptr.as_ptr().add(self.front),
+                self.back - self.front,
+            )
+        }
+    }
+}
+
+impl<'a, T, SlimmerMetadata> Iterator for Drain<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        // SAFETY: The element is initialized and will not be yielded or dropped again
+        let element = unsafe { self.vec.ptr.as_ptr().add(self.front).read() };
+        self.front += 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, SlimmerMetadata> DoubleEndedIterator for Drain<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        // SAFETY: The element is initialized and will not be yielded or dropped again
+        Some(unsafe { self.vec.ptr.as_ptr().add(self.back).read() })
+    }
+}
+
+impl<'a, T, SlimmerMetadata> ExactSizeIterator for Drain<'a, T, SlimmerMetadata> where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>
+{
+}
+
+impl<'a, T, SlimmerMetadata> core::iter::FusedIterator for Drain<'a, T, SlimmerMetadata> where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>
+{
+}
+
+impl<'a, T, SlimmerMetadata> Drop for Drain<'a, T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn drop(&mut self) {
+        /// Moves the tail back into place, even if dropping one of the remaining elements panics.
+        struct MoveTail<'r, 'a, T, SlimmerMetadata>(&'r mut Drain<'a, T, SlimmerMetadata>)
+        where
+            [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+            SlimmerMetadata: SlimMetadata<usize>;
+
+        impl<'r, 'a, T, SlimmerMetadata> Drop for MoveTail<'r, 'a, T, SlimmerMetadata>
+        where
+            [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+            SlimmerMetadata: SlimMetadata<usize>,
+        {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let start = drain.vec.len();
+                // SAFETY: The tail is initialized, and start + tail_len <= the original length
+                unsafe {
+                    let ptr = drain.vec.ptr.as_ptr();
+                    core::ptr::copy(ptr.add(drain.tail_start), ptr.add(start), drain.tail_len);
+                    drain.vec.set_len(start + drain.tail_len);
+                }
+            }
+        }
+
+        let guard = MoveTail(self);
+        let drain = &mut *guard.0;
+        let remaining = core::ptr::slice_from_raw_parts_mut(
+            // SAFETY: front <= back <= the original length
+            unsafe { drain.vec.ptr.as_ptr().add(drain.front) },
+            drain.back - drain.front,
+        );
+        drain.front = drain.back;
+        // SAFETY: These elements were not yet yielded
+        unsafe { core::ptr::drop_in_place(remaining) };
+    }
+}
+
+unsafe impl<T: Send, SlimmerMetadata: Send> Send for SlimmerVec<T, SlimmerMetadata> where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>
+{
+}
+
+unsafe impl<T: Sync, SlimmerMetadata: Sync> Sync for SlimmerVec<T, SlimmerMetadata> where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>
+{
+}
+
+impl<T, SlimmerMetadata> Drop for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn drop(&mut self) {
+        // SAFETY: The first `len` elements are initialized
+        unsafe { core::ptr::drop_in_place(self.as_mut_slice()) };
+        if core::mem::size_of::<T>() != 0 && self.capacity() != 0 {
+            // SAFETY: We allocated with this exact layout
+            unsafe {
+                let layout = Layout::array::<T>(self.capacity()).unwrap_unchecked();
+                alloc::alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+            }
+        }
+    }
+}
+
+impl<T, SlimmerMetadata> Default for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, SlimmerMetadata> Deref for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, SlimmerMetadata> DerefMut for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, SlimmerMetadata> AsRef<[T]> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> AsMut<[T]> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> core::borrow::Borrow<[T]> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> core::borrow::BorrowMut<[T]> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, SlimmerMetadata> Extend<T> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, SlimmerMetadata> Extend<&'a T> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T, SlimmerMetadata> core::iter::FromIterator<T> for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a, T, SlimmerMetadata> IntoIterator for &'a SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, SlimmerMetadata> IntoIterator for &'a mut SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone, SlimmerMetadata> Clone for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn clone(&self) -> Self {
+        let mut vec = Self::new();
+        vec.extend_from_slice(self);
+        vec
+    }
+}
+
+impl<T: core::fmt::Debug, SlimmerMetadata> core::fmt::Debug for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: PartialEq, SlimmerMetadata> PartialEq for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<T: Eq, SlimmerMetadata> Eq for SlimmerVec<T, SlimmerMetadata> where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>
+{
+}
+
+impl<T: PartialOrd, SlimmerMetadata> PartialOrd for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T: Ord, SlimmerMetadata> Ord for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<T: core::hash::Hash, SlimmerMetadata> core::hash::Hash for SlimmerVec<T, SlimmerMetadata>
+where
+    [T]: SlimmerPointee<SlimmerMetadata> + Pointee<Metadata = usize>,
+    SlimmerMetadata: SlimMetadata<usize>,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(core::mem::size_of::<SlimmerVec<u64>>(), 16);
+        assert_eq!(core::mem::size_of::<Option<SlimmerVec<u64, u16>>>(), 12);
+    }
+
+    #[test]
+    fn push_insert_remove() {
+        let mut vec: SlimmerVec<std::string::String> = SlimmerVec::new();
+        vec.push("b".into());
+        vec.insert(0, "a".into());
+        vec.push("c".into());
+        vec.push("d".into());
+        assert_eq!(vec.remove(1), "b");
+        assert_eq!(vec.swap_remove(0), "a");
+        assert_eq!(vec.pop().as_deref(), Some("c"));
+        assert_eq!(&*vec, &["d"]);
+    }
+
+    #[test]
+    fn stops_growing_at_metadata_max() {
+        let mut vec: SlimmerVec<u8, u8> = (0..254).collect();
+        vec.push(254);
+        assert_eq!(vec.len(), 255);
+        assert_eq!(vec.capacity(), 255);
+        assert!(vec.try_reserve(1).is_err());
+    }
+
+    #[test]
+    fn drain_and_retain() {
+        let mut vec: SlimmerVec<u32> = (0..10).collect();
+        let drained: std::vec::Vec<_> = vec.drain(2..5).collect();
+        assert_eq!(drained, [2, 3, 4]);
+        assert_eq!(&*vec, &[0, 1, 5, 6, 7, 8, 9]);
+
+        // Partially consumed drain still removes the whole range:
+        let mut drain = vec.drain(..3);
+        assert_eq!(drain.next_back(), Some(5));
+        drop(drain);
+        assert_eq!(&*vec, &[6, 7, 8, 9]);
+
+        vec.retain(|x| x % 2 == 0);
+        assert_eq!(&*vec, &[6, 8]);
+    }
+
+    #[test]
+    fn into_slimmer_box_and_back() {
+        let mut vec: SlimmerVec<u64, u16> = SlimmerVec::with_capacity(100);
+        vec.extend_from_slice(&[1, 2, 3]);
+        let boxed = vec.into_slimmer_box();
+        assert_eq!(&*boxed, &[1, 2, 3]);
+        let mut vec = SlimmerVec::from_slimmer_box(boxed);
+        vec.clear();
+        let boxed = vec.into_slimmer_box();
+        assert_eq!(&*boxed, &[] as &[u64]);
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let mut vec: SlimmerVec<(), u8> = SlimmerVec::new();
+        vec.extend((0..255).map(|_| ()));
+        assert!(vec.try_reserve(1).is_err());
+        assert_eq!(vec.into_slimmer_box().len(), 255);
+
+        let mut vec: SlimmerVec<(), u8> = SlimmerVec::new();
+        assert_eq!(vec.capacity(), 255);
+        // SAFETY: Zero-sized elements are always initialized, and 255 <= capacity
+        unsafe { vec.set_len(255) };
+        assert_eq!(vec.len(), 255);
+    }
+}